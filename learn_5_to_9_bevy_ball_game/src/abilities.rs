@@ -0,0 +1,176 @@
+use crate::imports::*;
+use bevy::prelude::*;
+use std::time::Duration;
+
+pub struct AbilityPlugin;
+
+impl Plugin for AbilityPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Dash>()
+            .register_type::<Shield>()
+            .add_systems(
+                FixedUpdate,
+                (
+                    tick_abilities,
+                    trigger_dash,
+                    trigger_shield,
+                    shield_reflect_enemies,
+                    update_ability_colors,
+                )
+                    .chain()
+                    .before(PlayerSystemSet::Movement)
+                    .run_if(in_state(AppState::Game))
+                    .run_if(in_state(SimulationState::Running)),
+            );
+    }
+}
+
+// Both timers start out finished, so a fresh ability is ready and not active
+#[derive(Reflect, Debug, Clone)]
+pub struct Ability {
+    pub duration: Timer,
+    pub cooldown: Timer,
+}
+
+impl Ability {
+    pub fn new(duration: f32, cooldown: f32) -> Self {
+        let mut duration = Timer::from_seconds(duration, TimerMode::Once);
+        let mut cooldown = Timer::from_seconds(cooldown, TimerMode::Once);
+        duration.tick(duration.duration());
+        cooldown.tick(cooldown.duration());
+
+        Self { duration, cooldown }
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.cooldown.finished()
+    }
+
+    pub fn is_active(&self) -> bool {
+        !self.duration.finished()
+    }
+
+    pub fn trigger(&mut self) -> bool {
+        if !self.is_ready() {
+            return false;
+        }
+        self.duration.reset();
+        self.cooldown.reset();
+        true
+    }
+
+    pub fn tick(&mut self, delta: Duration) {
+        self.duration.tick(delta);
+        self.cooldown.tick(delta);
+    }
+}
+
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component, Default)]
+pub struct Dash {
+    pub ability: Ability,
+    pub direction: Vec2,
+}
+
+impl Default for Dash {
+    fn default() -> Self {
+        Self {
+            ability: Ability::new(DASH_DURATION, DASH_COOLDOWN),
+            direction: Vec2::ZERO,
+        }
+    }
+}
+
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component, Default)]
+pub struct Shield {
+    pub ability: Ability,
+}
+
+impl Default for Shield {
+    fn default() -> Self {
+        Self {
+            ability: Ability::new(SHIELD_DURATION, SHIELD_COOLDOWN),
+        }
+    }
+}
+
+pub fn tick_abilities(mut player_query: Query<(&mut Dash, &mut Shield)>, time: Res<Time>) {
+    for (mut dash, mut shield) in player_query.iter_mut() {
+        dash.ability.tick(time.delta());
+        shield.ability.tick(time.delta());
+    }
+}
+
+pub fn trigger_dash(
+    player_input: Res<PlayerInput>,
+    mut player_query: Query<&mut Dash, With<Player>>,
+    mut sound_event_writer: EventWriter<SoundEvent>,
+) {
+    if !player_input.dash || player_input.movement == Vec2::ZERO {
+        return;
+    }
+    if let Ok(mut dash) = player_query.single_mut()
+        && dash.ability.trigger()
+    {
+        dash.direction = player_input.movement.normalize();
+        sound_event_writer.write(SoundEvent::Dash);
+    }
+}
+
+pub fn trigger_shield(
+    player_input: Res<PlayerInput>,
+    mut player_query: Query<&mut Shield, With<Player>>,
+    mut sound_event_writer: EventWriter<SoundEvent>,
+) {
+    if !player_input.shield {
+        return;
+    }
+    if let Ok(mut shield) = player_query.single_mut()
+        && shield.ability.trigger()
+    {
+        sound_event_writer.write(SoundEvent::Shield);
+    }
+}
+
+pub fn shield_reflect_enemies(
+    player_query: Query<(&Transform, &Shield), With<Player>>,
+    mut enemy_query: Query<(&mut Transform, &mut Enemy), Without<Player>>,
+) {
+    if let Ok((player_transform, shield)) = player_query.single() {
+        if !shield.ability.is_active() {
+            return;
+        }
+
+        let player_position = player_transform.translation.truncate();
+        let reach = SHIELD_RADIUS + ENEMY_SCALE / 2.0;
+
+        for (mut enemy_transform, mut enemy) in enemy_query.iter_mut() {
+            let offset = enemy_transform.translation.truncate() - player_position;
+
+            if offset.length() < reach {
+                let away = offset.try_normalize().unwrap_or(Vec2::X);
+
+                enemy.direction = away;
+                enemy_transform.translation =
+                    (player_position + away * reach).extend(enemy_transform.translation.z);
+            }
+        }
+    }
+}
+
+pub fn update_ability_colors(mut player_query: Query<(&mut Sprite, &Dash, &Shield), With<Player>>) {
+    if let Ok((mut sprite, dash, shield)) = player_query.single_mut() {
+        let color = if shield.ability.is_active() {
+            SHIELD_COLOR
+        } else if dash.ability.is_active() {
+            DASH_COLOR
+        } else {
+            Color::WHITE
+        };
+
+        if sprite.color != color {
+            sprite.color = color;
+        }
+    }
+}
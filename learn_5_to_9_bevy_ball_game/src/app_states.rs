@@ -1,5 +1,3 @@
-use std::ops::Not;
-
 use crate::imports::*;
 use bevy::prelude::*;
 
@@ -11,6 +9,14 @@ pub enum AppState {
     GameOver,
 }
 
+// Whether the settings screen is open on top of the main menu or the pause screen
+#[derive(States, Debug, Hash, Eq, PartialEq, Clone, Default)]
+pub enum SettingsState {
+    #[default]
+    Closed,
+    Open,
+}
+
 pub struct StatesPlugin;
 
 impl Plugin for StatesPlugin {
@@ -21,7 +27,8 @@ impl Plugin for StatesPlugin {
                 toggle_simulation.run_if(in_state(AppState::Game)),
                 transition_to_game_state,
                 transition_to_main_menu_state,
-            ),
+            )
+                .run_if(in_state(SettingsState::Closed)),
         );
     }
 }
@@ -30,14 +37,16 @@ pub fn toggle_simulation(
     mut commands: Commands,
     keyboard_input: Res<ButtonInput<KeyCode>>,
     simulation_state: Res<State<SimulationState>>,
+    mut sound_event_writer: EventWriter<SoundEvent>,
 ) {
     if keyboard_input.just_pressed(KeyCode::Space) {
+        sound_event_writer.write(SoundEvent::UiClick);
         if simulation_state.eq(&SimulationState::Running) {
             commands.insert_resource(NextState::Pending(SimulationState::Paused));
-            println!("Simulation paused");
+            info!(state = ?SimulationState::Paused, "Simulation paused");
         } else {
             commands.insert_resource(NextState::Pending(SimulationState::Running));
-            println!("Simulation continued");
+            info!(state = ?SimulationState::Running, "Simulation continued");
         }
     }
 }
@@ -46,29 +55,24 @@ pub fn transition_to_game_state(
     mut commands: Commands,
     keyboard_input: Res<ButtonInput<KeyCode>>,
     app_state: Res<State<AppState>>,
+    mut sound_event_writer: EventWriter<SoundEvent>,
 ) {
-    if keyboard_input.just_pressed(KeyCode::KeyG) {
-        if app_state.get().ne(&AppState::Game) {
-            commands.insert_resource(NextState::Pending(AppState::Game));
-            println!("Transitioning to game state")
-        }
-        //  else {
-        //     commands.insert_resource(NextState::Pending(AppState::MainMenu));
-        // }
+    // The game over screen uses G to go back to the main menu, not to play again
+    if keyboard_input.just_pressed(KeyCode::KeyG) && app_state.get().eq(&AppState::MainMenu) {
+        commands.insert_resource(NextState::Pending(AppState::Game));
+        sound_event_writer.write(SoundEvent::UiClick);
+        info!(state = ?AppState::Game, "Transitioning to game state");
     }
 }
 pub fn transition_to_main_menu_state(
     mut commands: Commands,
     keyboard_input: Res<ButtonInput<KeyCode>>,
     app_state: Res<State<AppState>>,
+    mut sound_event_writer: EventWriter<SoundEvent>,
 ) {
-    if keyboard_input.just_pressed(KeyCode::KeyG) {
-        if app_state.get().ne(&AppState::MainMenu) {
-            commands.insert_resource(NextState::Pending(AppState::MainMenu));
-            println!("Transitioning to main menu state")
-        }
-        //  else {
-        //     commands.insert_resource(NextState::Pending(AppState::MainMenu));
-        // }
+    if keyboard_input.just_pressed(KeyCode::KeyG) && app_state.get().ne(&AppState::MainMenu) {
+        commands.insert_resource(NextState::Pending(AppState::MainMenu));
+        sound_event_writer.write(SoundEvent::UiClick);
+        info!(state = ?AppState::MainMenu, "Transitioning to main menu state");
     }
 }
@@ -0,0 +1,168 @@
+use crate::imports::*;
+use bevy::audio::Volume;
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+use rand::Rng;
+use rand::seq::IndexedRandom;
+use serde::Deserialize;
+use std::io;
+use std::path::Path;
+
+pub struct SoundPlugin;
+
+impl Plugin for SoundPlugin {
+    fn build(&self, app: &mut App) {
+        let path = asset_file_path(SOUND_TABLE_PATH);
+        let sound_table = SoundTable::load(&path).unwrap_or_else(|error| {
+            error!(path = %path.display(), %error, "Could not load the sound table");
+            SoundTable::default()
+        });
+
+        app.insert_resource(sound_table)
+            .init_resource::<AudioVolumes>()
+            .init_resource::<SoundLimiter>()
+            .add_event::<SoundEvent>()
+            .add_systems(Update, (play_sounds, apply_audio_volumes));
+    }
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum AudioBus {
+    Sfx,
+    Music,
+    Ui,
+}
+
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct AudioVolumes {
+    pub master: f32,
+    pub sfx: f32,
+    pub music: f32,
+    pub ui: f32,
+}
+
+impl Default for AudioVolumes {
+    fn default() -> Self {
+        Self {
+            master: MASTER_VOLUME,
+            sfx: SFX_VOLUME,
+            music: MUSIC_VOLUME,
+            ui: UI_VOLUME,
+        }
+    }
+}
+
+impl AudioVolumes {
+    // What a sound on `bus` plays at, after the master volume
+    pub fn volume(&self, bus: AudioBus) -> f32 {
+        let bus_volume = match bus {
+            AudioBus::Sfx => self.sfx,
+            AudioBus::Music => self.music,
+            AudioBus::Ui => self.ui,
+        };
+        self.master * bus_volume
+    }
+}
+
+// What each sound event plays, read from a data file so sounds can be changed without
+// touching the systems that emit them
+#[derive(Resource, Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(transparent)]
+pub struct SoundTable(pub HashMap<SoundEvent, SoundClips>);
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SoundClips {
+    pub bus: AudioBus,
+    pub clips: Vec<String>,
+    #[serde(default)]
+    pub pitch_jitter: f32,
+}
+
+impl SoundTable {
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        ron::from_str(&std::fs::read_to_string(path)?)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+}
+
+// Marks an audio player spawned for a sound event, so its instances can be counted
+#[derive(Component, Debug)]
+pub struct SoundInstance {
+    pub sound: SoundEvent,
+}
+
+// When each sound event last started playing
+#[derive(Resource, Debug, Default)]
+pub struct SoundLimiter {
+    pub last_played: HashMap<SoundEvent, f32>,
+}
+
+// Fifty enemies bouncing in the same frame should sound like one bounce, not fifty
+#[allow(clippy::too_many_arguments)]
+pub fn play_sounds(
+    mut commands: Commands,
+    mut sound_event_reader: EventReader<SoundEvent>,
+    mut sound_limiter: ResMut<SoundLimiter>,
+    instance_query: Query<&SoundInstance>,
+    sound_table: Res<SoundTable>,
+    assest_server: Res<AssetServer>,
+    audio_volumes: Res<AudioVolumes>,
+    time: Res<Time<Real>>,
+) {
+    let now = time.elapsed_secs();
+    // Variants are picked with their own generator, the game's would make runs depend on
+    // what was heard
+    let mut rng = rand::rng();
+
+    for sound in sound_event_reader.read() {
+        let Some(sound_clips) = sound_table.0.get(sound) else {
+            continue;
+        };
+        let Some(clip) = sound_clips.clips.choose(&mut rng) else {
+            continue;
+        };
+
+        if let Some(last_played) = sound_limiter.last_played.get(sound)
+            && now - last_played < SOUND_MIN_INTERVAL
+        {
+            continue;
+        }
+
+        let instances = instance_query
+            .iter()
+            .filter(|instance| instance.sound == *sound)
+            .count();
+        if instances >= SOUND_MAX_INSTANCES {
+            continue;
+        }
+
+        sound_limiter.last_played.insert(*sound, now);
+
+        let jitter = sound_clips.pitch_jitter;
+        let speed = 1.0 + rng.random_range(-jitter..=jitter);
+
+        // Despawns itself once the sound has finished
+        commands.spawn((
+            AudioPlayer::new(assest_server.load(clip)),
+            PlaybackSettings::DESPAWN
+                .with_volume(Volume::Linear(audio_volumes.volume(sound_clips.bus)))
+                .with_speed(speed),
+            sound_clips.bus,
+            SoundInstance { sound: *sound },
+        ));
+    }
+}
+
+// Sounds that are already playing follow volume changes too. Music fades its own volume.
+pub fn apply_audio_volumes(
+    audio_volumes: Res<AudioVolumes>,
+    mut sink_query: Query<(&mut AudioSink, &AudioBus), Without<MusicTrack>>,
+) {
+    if !audio_volumes.is_changed() {
+        return;
+    }
+
+    for (mut sink, bus) in &mut sink_query {
+        sink.set_volume(Volume::Linear(audio_volumes.volume(*bus)));
+    }
+}
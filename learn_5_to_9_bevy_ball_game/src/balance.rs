@@ -0,0 +1,22 @@
+use crate::imports::*;
+use bevy::prelude::*;
+use std::ops::Range;
+
+// The settings a balance sweep varies, which the game reads at runtime instead of the
+// constants in settings.rs
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct Balance {
+    pub enemy_spawn_time: f32,
+    pub enemy_speed: Range<f32>,
+    pub star_spawn_time: f32,
+}
+
+impl Default for Balance {
+    fn default() -> Self {
+        Self {
+            enemy_spawn_time: ENEMY_SPAWN_TIMER,
+            enemy_speed: ENEMY_SPEED_VARIATY,
+            star_spawn_time: STAR_SPAWN_TIME,
+        }
+    }
+}
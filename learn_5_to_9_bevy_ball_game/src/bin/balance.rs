@@ -0,0 +1,130 @@
+// Plays many seeded bot games for every combination of the swept settings and writes
+// how long the bot survived and what it scored to a CSV file.
+//
+// cargo run --release --bin balance -- --enemy-spawn-time 1,2,3 --enemy-speed 25..150,25..200
+//     --star-spawn-time 2,3 --runs 20 --seconds 60 --output balance.csv
+//
+// Settings that aren't swept keep their value from settings.rs.
+
+use learn_2_bevy_ball_game::*;
+use std::fmt::Write as _;
+use std::ops::Range;
+use std::str::FromStr;
+
+const DEFAULT_RUNS: u64 = 20;
+const DEFAULT_SECONDS: f32 = 60.0;
+const DEFAULT_OUTPUT: &str = "balance.csv";
+
+const CSV_HEADER: &str = "enemy_spawn_time,enemy_speed_min,enemy_speed_max,star_spawn_time,\
+runs,game_over_rate,mean_survival,median_survival,mean_score,median_score";
+
+fn main() {
+    let default_balance = Balance::default();
+
+    let enemy_spawn_times = values_arg("--enemy-spawn-time", default_balance.enemy_spawn_time);
+    let enemy_speeds = range_values_arg("--enemy-speed", default_balance.enemy_speed.clone());
+    let star_spawn_times = values_arg("--star-spawn-time", default_balance.star_spawn_time);
+
+    let runs = parsed_arg("--runs", DEFAULT_RUNS);
+    let seconds = parsed_arg("--seconds", DEFAULT_SECONDS);
+    let first_seed = seed_from_args().unwrap_or(0);
+    let output = cli_arg("--output").unwrap_or_else(|| DEFAULT_OUTPUT.to_string());
+
+    let mut points = Vec::new();
+    for enemy_spawn_time in &enemy_spawn_times {
+        for enemy_speed in &enemy_speeds {
+            for star_spawn_time in &star_spawn_times {
+                points.push(Balance {
+                    enemy_spawn_time: *enemy_spawn_time,
+                    enemy_speed: enemy_speed.clone(),
+                    star_spawn_time: *star_spawn_time,
+                });
+            }
+        }
+    }
+
+    let mut csv = format!("{}\n", CSV_HEADER);
+
+    for (index, balance) in points.iter().enumerate() {
+        println!("Sweeping {}/{}: {:?}", index + 1, points.len(), balance);
+
+        let reports = simulate_seeds(first_seed..first_seed + runs, seconds, true, balance);
+        let survived: Vec<f32> = reports
+            .iter()
+            .map(|report| report.survived_seconds)
+            .collect();
+        let scores: Vec<f32> = reports.iter().map(|report| report.score as f32).collect();
+        let game_overs = reports.iter().filter(|report| report.game_over).count();
+
+        let survived = Distribution::of(&survived);
+        let scores = Distribution::of(&scores);
+
+        writeln!(
+            csv,
+            "{},{},{},{},{},{:.3},{:.2},{:.2},{:.2},{:.2}",
+            balance.enemy_spawn_time,
+            balance.enemy_speed.start,
+            balance.enemy_speed.end,
+            balance.star_spawn_time,
+            runs,
+            game_overs as f32 / runs as f32,
+            survived.mean,
+            survived.median,
+            scores.mean,
+            scores.median
+        )
+        .unwrap();
+    }
+
+    match std::fs::write(&output, csv) {
+        Ok(()) => println!("Wrote {} sweep points to {}", points.len(), output),
+        Err(error) => {
+            eprintln!("Could not write {}: {}", output, error);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn parsed_arg<T: FromStr>(name: &str, default: T) -> T {
+    cli_arg(name).map_or(default, |value| {
+        value
+            .parse()
+            .unwrap_or_else(|_| panic!("{} expects a number, got {}", name, value))
+    })
+}
+
+// A comma separated list, like `1,2.5,4`
+fn values_arg(name: &str, default: f32) -> Vec<f32> {
+    cli_arg(name).map_or(vec![default], |values| {
+        values
+            .split(',')
+            .map(|value| {
+                value
+                    .trim()
+                    .parse()
+                    .unwrap_or_else(|_| panic!("{} expects numbers, got {}", name, value))
+            })
+            .collect()
+    })
+}
+
+// A comma separated list of ranges, like `25..150,50..300`
+fn range_values_arg(name: &str, default: Range<f32>) -> Vec<Range<f32>> {
+    cli_arg(name).map_or(vec![default], |values| {
+        values
+            .split(',')
+            .map(|value| {
+                let parsed = value
+                    .split_once("..")
+                    .and_then(|(start, end)| {
+                        Some(start.trim().parse().ok()?..end.trim().parse().ok()?)
+                    })
+                    .filter(|range: &Range<f32>| !range.is_empty());
+
+                parsed.unwrap_or_else(|| {
+                    panic!("{} expects ranges like 25..200, got {}", name, value)
+                })
+            })
+            .collect()
+    })
+}
@@ -0,0 +1,159 @@
+use crate::imports::*;
+use bevy::prelude::*;
+
+// Takes over `PlayerInput` from the keyboard, for playtesting without a player
+pub struct BotPlugin;
+
+impl Plugin for BotPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedPreUpdate,
+            steer_bot
+                .before(record_player_input)
+                .run_if(in_state(AppState::Game))
+                .run_if(in_state(SimulationState::Running)),
+        );
+    }
+}
+
+// The bot only presses what a keyboard could, so its runs can be recorded as replays
+const BOT_DIRECTIONS: [Vec2; 9] = [
+    Vec2::ZERO,
+    Vec2::new(0.0, 1.0),
+    Vec2::new(1.0, 1.0),
+    Vec2::new(1.0, 0.0),
+    Vec2::new(1.0, -1.0),
+    Vec2::new(0.0, -1.0),
+    Vec2::new(-1.0, -1.0),
+    Vec2::new(-1.0, 0.0),
+    Vec2::new(-1.0, 1.0),
+];
+
+#[derive(Debug, Clone, Copy)]
+pub struct BotEnemy {
+    pub position: Vec2,
+    pub velocity: Vec2,
+}
+
+pub fn steer_bot(
+    player_query: Query<&Transform, With<Player>>,
+    star_query: Query<&Transform, With<Star>>,
+    enemy_query: Query<(&Transform, &Enemy)>,
+    arena: Res<Arena>,
+    mut player_input: ResMut<PlayerInput>,
+) {
+    let Ok(player_transform) = player_query.single() else {
+        *player_input = PlayerInput::default();
+        return;
+    };
+
+    let stars: Vec<Vec2> = star_query
+        .iter()
+        .map(|star_transform| star_transform.translation.truncate())
+        .collect();
+    let enemies: Vec<BotEnemy> = enemy_query
+        .iter()
+        .map(|(enemy_transform, enemy)| BotEnemy {
+            position: enemy_transform.translation.truncate(),
+            velocity: enemy.direction * enemy.speed,
+        })
+        .collect();
+
+    *player_input = bot_input(
+        player_transform.translation.truncate(),
+        &stars,
+        &enemies,
+        &arena,
+    );
+}
+
+// Tries every key combination and keeps the one that gets closest to the nearest star
+// without running into where the enemies are heading
+pub fn bot_input(player: Vec2, stars: &[Vec2], enemies: &[BotEnemy], arena: &Arena) -> PlayerInput {
+    let target = stars
+        .iter()
+        .min_by(|a, b| a.distance(player).total_cmp(&b.distance(player)));
+    let step = BOT_LOOKAHEAD_TIME / BOT_LOOKAHEAD_STEPS as f32;
+
+    let mut best_input = PlayerInput::default();
+    let mut best_value = f32::NEG_INFINITY;
+    let mut best_collision = None;
+
+    for movement in BOT_DIRECTIONS {
+        for sprint in [false, true] {
+            if movement == Vec2::ZERO && sprint {
+                continue;
+            }
+
+            let speed = if sprint {
+                PLAYER_SPEED * SPRINT_MULTIPLIER
+            } else {
+                PLAYER_SPEED
+            };
+            let velocity = movement.normalize_or_zero() * speed;
+
+            let progress = target.map_or(0.0, |target| {
+                player.distance(*target)
+                    - confine_bot(player + velocity * step, arena).distance(*target)
+            });
+            let (danger, collision) = predict_danger(player, velocity, enemies, arena);
+            let value = progress - danger * BOT_DANGER_WEIGHT;
+
+            if value > best_value {
+                best_value = value;
+                best_collision = collision;
+                best_input = PlayerInput {
+                    movement,
+                    sprint,
+                    ..default()
+                };
+            }
+        }
+    }
+
+    best_input.shield = best_collision.is_some_and(|time| time <= BOT_SHIELD_TIME);
+    best_input
+}
+
+// Sums how deep into the safety margin each enemy gets over the lookahead, weighting
+// sooner encounters more, and returns when the first collision would happen
+fn predict_danger(
+    player: Vec2,
+    velocity: Vec2,
+    enemies: &[BotEnemy],
+    arena: &Arena,
+) -> (f32, Option<f32>) {
+    let collision_distance = PLAYER_SCALE / 2.0 + ENEMY_SCALE / 2.0;
+    let step = BOT_LOOKAHEAD_TIME / BOT_LOOKAHEAD_STEPS as f32;
+
+    let mut danger = 0.0;
+    let mut collision = None;
+
+    for i in 1..=BOT_LOOKAHEAD_STEPS {
+        let time = step * i as f32;
+        let position = confine_bot(player + velocity * time, arena);
+
+        for enemy in enemies {
+            let enemy_position = confine_bot(enemy.position + enemy.velocity * time, arena);
+            let clearance = position.distance(enemy_position) - collision_distance;
+
+            if clearance < BOT_SAFETY_MARGIN {
+                danger += (1.0 - clearance / BOT_SAFETY_MARGIN) / i as f32;
+            }
+            if clearance < 0.0 && collision.is_none() {
+                collision = Some(time);
+            }
+        }
+    }
+
+    (danger, collision)
+}
+
+fn confine_bot(position: Vec2, arena: &Arena) -> Vec2 {
+    let half_player_size = PLAYER_SCALE / 2.0;
+
+    position.clamp(
+        Vec2::splat(half_player_size),
+        Vec2::new(arena.width, arena.height) - half_player_size,
+    )
+}
@@ -0,0 +1,115 @@
+use crate::imports::*;
+use bevy::prelude::*;
+use bevy::render::camera::{ScalingMode, Viewport};
+use bevy::render::view::RenderLayers;
+use bevy::window::PrimaryWindow;
+
+// Shows the same part of the arena at any window size. The view is scaled to fit and the
+// space around it is filled with bars, so resizing the window never changes the playfield.
+// An arena larger than the view scrolls with the player.
+pub struct ArenaCameraPlugin;
+
+impl Plugin for ArenaCameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_camera)
+            .add_systems(Update, (letterbox_camera, follow_player));
+    }
+}
+
+#[derive(Component)]
+pub struct GameCamera;
+
+pub fn spawn_camera(mut commands: Commands, arena: Res<Arena>) {
+    let view_size = arena.view_size();
+    let position = camera_position(arena.size() / 2.0, &arena);
+
+    commands.spawn((
+        Camera2d,
+        Camera {
+            hdr: true,
+            ..default()
+        },
+        Projection::from(OrthographicProjection {
+            scaling_mode: ScalingMode::Fixed {
+                width: view_size.x,
+                height: view_size.y,
+            },
+            ..OrthographicProjection::default_2d()
+        }),
+        Transform::from_translation(position.extend(0.0)),
+        IsDefaultUiCamera,
+        GameCamera,
+    ));
+
+    // Draws nothing, it only clears the window around the arena
+    commands.spawn((
+        Camera2d,
+        Camera {
+            hdr: true,
+            order: -1,
+            clear_color: ClearColorConfig::Custom(LETTERBOX_COLOR),
+            ..default()
+        },
+        RenderLayers::layer(LETTERBOX_RENDER_LAYER),
+    ));
+}
+
+pub fn letterbox_camera(
+    window_query: Query<&Window, (With<PrimaryWindow>, Changed<Window>)>,
+    mut camera_query: Query<&mut Camera, With<GameCamera>>,
+    arena: Res<Arena>,
+) {
+    let Ok(window) = window_query.single() else {
+        return;
+    };
+    let Ok(mut camera) = camera_query.single_mut() else {
+        return;
+    };
+
+    // A minimized window has no size to fit the view into
+    if let Some((physical_position, physical_size)) = letterbox(window.physical_size(), &arena) {
+        camera.viewport = Some(Viewport {
+            physical_position,
+            physical_size,
+            ..default()
+        });
+    }
+}
+
+// The largest part of the window with the view's aspect ratio, centered
+pub fn letterbox(window_size: UVec2, arena: &Arena) -> Option<(UVec2, UVec2)> {
+    if window_size.x == 0 || window_size.y == 0 {
+        return None;
+    }
+
+    let view_size = arena.view_size();
+    let window_size = window_size.as_vec2();
+    let scale = (window_size.x / view_size.x).min(window_size.y / view_size.y);
+    let size = (view_size * scale).round().clamp(Vec2::ONE, window_size);
+    let position = ((window_size - size) / 2.0).floor();
+
+    Some((position.as_uvec2(), size.as_uvec2()))
+}
+
+pub fn follow_player(
+    player_query: Query<&Transform, (With<Player>, Without<GameCamera>)>,
+    mut camera_query: Query<&mut Transform, With<GameCamera>>,
+    arena: Res<Arena>,
+) {
+    let Ok(mut camera_transform) = camera_query.single_mut() else {
+        return;
+    };
+    // Without a player, like after a game over, the camera stays where it is
+    let Ok(player_transform) = player_query.single() else {
+        return;
+    };
+
+    let position = camera_position(player_transform.translation.truncate(), &arena);
+    camera_transform.translation = position.extend(camera_transform.translation.z);
+}
+
+// Centers the view on `target` without showing anything outside the arena
+pub fn camera_position(target: Vec2, arena: &Arena) -> Vec2 {
+    let half_view = arena.view_size() / 2.0;
+    target.clamp(half_view, arena.size() - half_view)
+}
@@ -0,0 +1,284 @@
+use crate::imports::*;
+use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
+use bevy::ecs::entity::Entities;
+use bevy::input::common_conditions::input_just_pressed;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+pub struct DebugOverlayPlugin;
+
+impl Plugin for DebugOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+            app.add_plugins(FrameTimeDiagnosticsPlugin::default());
+        }
+
+        app.init_resource::<DebugOverlay>()
+            .add_systems(Startup, spawn_debug_overlay)
+            .add_systems(
+                Update,
+                (
+                    toggle_debug_overlay.run_if(input_just_pressed(DEBUG_OVERLAY_KEY)),
+                    count_collected_stars,
+                    (
+                        select_inspected_entity.run_if(input_just_pressed(MouseButton::Left)),
+                        update_debug_stats,
+                        update_inspector_panel,
+                        draw_debug_gizmos,
+                    )
+                        .chain()
+                        .run_if(debug_overlay_enabled),
+                )
+                    .chain(),
+            );
+    }
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct DebugOverlay {
+    pub enabled: bool,
+    pub inspected: Option<Entity>,
+    pub stars_collected: u32,
+}
+
+#[derive(Component)]
+pub struct DebugOverlayPanel;
+
+#[derive(Component)]
+pub struct DebugStatsText;
+
+#[derive(Component)]
+pub struct DebugInspectorText;
+
+pub fn debug_overlay_enabled(debug_overlay: Res<DebugOverlay>) -> bool {
+    debug_overlay.enabled
+}
+
+pub fn spawn_debug_overlay(mut commands: Commands) {
+    commands
+        .spawn(debug_panel(Node {
+            left: Val::Px(HUD_MARGIN),
+            ..default()
+        }))
+        .with_child((debug_text(), DebugStatsText));
+
+    commands
+        .spawn(debug_panel(Node {
+            right: Val::Px(HUD_MARGIN),
+            width: Val::Px(DEBUG_PANEL_WIDTH),
+            ..default()
+        }))
+        .with_child((debug_text(), DebugInspectorText));
+}
+
+fn debug_panel(node: Node) -> impl Bundle {
+    (
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(HUD_MARGIN),
+            padding: UiRect::all(Val::Px(HUD_MARGIN)),
+            ..node
+        },
+        BackgroundColor(DEBUG_BACKGROUND_COLOR),
+        Visibility::Hidden,
+        DebugOverlayPanel,
+    )
+}
+
+fn debug_text() -> impl Bundle {
+    (
+        Text::new(""),
+        TextFont {
+            font_size: DEBUG_FONT_SIZE,
+            ..default()
+        },
+    )
+}
+
+pub fn toggle_debug_overlay(
+    mut debug_overlay: ResMut<DebugOverlay>,
+    mut panel_query: Query<&mut Visibility, With<DebugOverlayPanel>>,
+) {
+    debug_overlay.enabled = !debug_overlay.enabled;
+    if !debug_overlay.enabled {
+        debug_overlay.inspected = None;
+    }
+
+    for mut visibility in &mut panel_query {
+        *visibility = if debug_overlay.enabled {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+// Counted even while the overlay is hidden, so the total is right when it's shown
+pub fn count_collected_stars(
+    mut score_event_reader: EventReader<ScoreEvent>,
+    mut debug_overlay: ResMut<DebugOverlay>,
+) {
+    for score_event in score_event_reader.read() {
+        if score_event.kind == ScoreKind::Star {
+            debug_overlay.stars_collected += 1;
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn update_debug_stats(
+    diagnostics: Res<DiagnosticsStore>,
+    entities: &Entities,
+    player_input: Res<PlayerInput>,
+    debug_overlay: Res<DebugOverlay>,
+    player_query: Query<(), With<Player>>,
+    enemy_query: Query<(), With<Enemy>>,
+    star_query: Query<(), With<Star>>,
+    mut text_query: Query<&mut Text, With<DebugStatsText>>,
+) {
+    let Ok(mut text) = text_query.single_mut() else {
+        return;
+    };
+
+    let fps = diagnostics
+        .get(&FrameTimeDiagnosticsPlugin::FPS)
+        .and_then(|fps| fps.smoothed())
+        .unwrap_or_default();
+
+    text.0 = format!(
+        "FPS: {:.0}\nEntities: {}\nPlayers: {}\nEnemies: {}\nStars: {}\n\
+         Input: ({:.0}, {:.0}){}\nStars collected: {}\n\nClick an entity to inspect it",
+        fps,
+        entities.len(),
+        player_query.iter().count(),
+        enemy_query.iter().count(),
+        star_query.iter().count(),
+        player_input.movement.x,
+        player_input.movement.y,
+        if player_input.sprint { " sprint" } else { "" },
+        debug_overlay.stars_collected,
+    );
+}
+
+#[allow(clippy::type_complexity)]
+pub fn select_inspected_entity(
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<GameCamera>>,
+    entity_query: Query<(Entity, &Transform), Or<(With<Player>, With<Enemy>, With<Star>)>>,
+    mut debug_overlay: ResMut<DebugOverlay>,
+) {
+    let Ok(window) = window_query.single() else {
+        return;
+    };
+    let Ok((camera, camera_transform)) = camera_query.single() else {
+        return;
+    };
+    let Some(cursor_position) = window
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor).ok())
+    else {
+        return;
+    };
+
+    debug_overlay.inspected = entity_query
+        .iter()
+        .map(|(entity, transform)| {
+            (
+                entity,
+                transform.translation.truncate().distance(cursor_position),
+            )
+        })
+        .filter(|(_, distance)| *distance < DEBUG_INSPECT_DISTANCE)
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(entity, _)| entity);
+}
+
+// The reflected gameplay components of an entity, by their short type name
+pub fn inspect_entity(world: &World, entity: Entity) -> Option<Vec<(String, String)>> {
+    let entity_ref = world.get_entity(entity).ok()?;
+    let type_registry = world.resource::<AppTypeRegistry>().read();
+
+    let mut components: Vec<(String, String)> = entity_ref
+        .archetype()
+        .components()
+        .filter_map(|component_id| {
+            let type_id = world.components().get_info(component_id)?.type_id()?;
+            let registration = type_registry.get(type_id)?;
+            let type_path = registration.type_info().type_path_table();
+
+            // Rendering components would bury the ones that matter for gameplay
+            if type_id != std::any::TypeId::of::<Transform>()
+                && type_path.crate_name() != Some(env!("CARGO_CRATE_NAME"))
+            {
+                return None;
+            }
+
+            let reflected = registration
+                .data::<ReflectComponent>()?
+                .reflect(entity_ref)?;
+            Some((
+                type_path.short_path().to_string(),
+                format!("{:#?}", reflected),
+            ))
+        })
+        .collect();
+
+    components.sort();
+    Some(components)
+}
+
+pub fn update_inspector_panel(world: &mut World) {
+    let text = match world.resource::<DebugOverlay>().inspected {
+        None => "Nothing inspected".to_string(),
+        Some(entity) => match inspect_entity(world, entity) {
+            None => format!("{} is gone", entity),
+            Some(components) => components
+                .into_iter()
+                .fold(format!("{}", entity), |text, (name, value)| {
+                    format!("{}\n\n{}: {}", text, name, value)
+                }),
+        },
+    };
+
+    for mut inspector_text in world
+        .query_filtered::<&mut Text, With<DebugInspectorText>>()
+        .iter_mut(world)
+    {
+        inspector_text.0.clone_from(&text);
+    }
+}
+
+pub fn draw_debug_gizmos(
+    mut gizmos: Gizmos,
+    debug_overlay: Res<DebugOverlay>,
+    player_query: Query<(Entity, &Transform), With<Player>>,
+    enemy_query: Query<(Entity, &Transform, &Enemy)>,
+    star_query: Query<(Entity, &Transform), With<Star>>,
+) {
+    let mut circles = Vec::new();
+
+    for (entity, transform) in &player_query {
+        circles.push((entity, transform, PLAYER_SCALE / 2.0, DEBUG_PLAYER_COLOR));
+    }
+    for (entity, transform, enemy) in &enemy_query {
+        let position = transform.translation.truncate();
+        gizmos.arrow_2d(
+            position,
+            position + enemy.direction * DEBUG_DIRECTION_LENGTH,
+            DEBUG_ENEMY_COLOR,
+        );
+        circles.push((entity, transform, ENEMY_SCALE / 2.0, DEBUG_ENEMY_COLOR));
+    }
+    for (entity, transform) in &star_query {
+        circles.push((entity, transform, STAR_SCALE / 2.0, DEBUG_STAR_COLOR));
+    }
+
+    for (entity, transform, radius, color) in circles {
+        let position = transform.translation.truncate();
+        gizmos.circle_2d(position, radius, color);
+
+        if debug_overlay.inspected == Some(entity) {
+            gizmos.circle_2d(position, radius + 4.0, DEBUG_INSPECTED_COLOR);
+        }
+    }
+}
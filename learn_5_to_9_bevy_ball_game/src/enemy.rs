@@ -1,14 +1,21 @@
 use crate::imports::*;
-use bevy::{prelude::*, window::PrimaryWindow};
-use rand::random;
+use bevy::prelude::*;
+use rand::Rng;
 
-#[derive(Component)]
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+#[require(TranslationInterpolation)]
 pub struct Enemy {
     pub direction: Vec2,
     pub speed: f32,
 }
 
-#[derive(Resource)]
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct NearMiss;
+
+#[derive(Resource, Reflect, Debug)]
+#[reflect(Resource)]
 pub struct EnemySpawnTime {
     pub timer: Timer,
 }
@@ -17,16 +24,20 @@ pub struct EnemyPlugin;
 
 impl Plugin for EnemyPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<EnemySpawnTime>()
+        app.register_type::<Enemy>()
+            .register_type::<NearMiss>()
+            .register_type::<EnemySpawnTime>()
+            .init_resource::<EnemySpawnTime>()
             .add_systems(Startup, spawn_enemies)
             .add_systems(
-                Update,
+                FixedUpdate,
                 (
                     tick_enemy_spawn_timer,
                     spawn_enemies_over_time,
                     enemy_movement,
                     confine_enemy_movement,
                     enemy_hit_player,
+                    enemy_near_miss_player,
                     update_enemy_direction,
                 )
                     .run_if(in_state(AppState::Game))
@@ -35,10 +46,12 @@ impl Plugin for EnemyPlugin {
     }
 }
 
-impl Default for EnemySpawnTime {
-    fn default() -> Self {
+impl FromWorld for EnemySpawnTime {
+    fn from_world(world: &mut World) -> Self {
+        let balance = world.resource::<Balance>();
+
         Self {
-            timer: Timer::from_seconds(ENEMY_SPAWN_TIMER, TimerMode::Repeating),
+            timer: Timer::from_seconds(balance.enemy_spawn_time, TimerMode::Repeating),
         }
     }
 }
@@ -46,32 +59,69 @@ impl Default for EnemySpawnTime {
 pub fn spawn_enemies(
     mut commands: Commands,
     assest_server: Res<AssetServer>,
-    windows_query: Query<&Window, With<PrimaryWindow>>,
+    arena: Res<Arena>,
+    balance: Res<Balance>,
+    mut game_rng: ResMut<GameRng>,
 ) {
-    let window = windows_query.single().unwrap();
-
-    (0..NUMBER_OF_ENEMIES).into_iter().for_each(|_| {
-        let random_x = random::<f32>() * window.width();
-        let random_y = random::<f32>() * window.height();
-
-        commands.spawn((
-            Sprite {
-                image: assest_server.load("sprites/ball_red_large.png"),
-                custom_size: Some(Vec2 {
-                    x: ENEMY_SCALE,
-                    y: ENEMY_SCALE,
-                }),
-                ..default()
-            },
-            Transform::from_xyz(random_x, random_y, 0.0),
-            Enemy {
-                direction: Vec2::new(random::<f32>(), random::<f32>()).normalize(),
-                speed: rand::random_range(ENEMY_SPEED_VARIATY),
-            },
-        ));
+    (0..NUMBER_OF_ENEMIES).for_each(|_| {
+        spawn_random_enemy(
+            &mut commands,
+            &assest_server,
+            &arena,
+            &balance,
+            &mut game_rng,
+        );
     });
 }
 
+pub fn spawn_enemy(
+    commands: &mut Commands,
+    assest_server: &AssetServer,
+    position: Vec2,
+    direction: Vec2,
+    speed: f32,
+) -> Entity {
+    commands
+        .spawn((
+            enemy_sprite(assest_server),
+            Transform::from_xyz(position.x, position.y, 0.0),
+            Enemy { direction, speed },
+        ))
+        .id()
+}
+
+pub fn enemy_sprite(assest_server: &AssetServer) -> Sprite {
+    Sprite {
+        image: assest_server.load("sprites/ball_red_large.png"),
+        custom_size: Some(Vec2 {
+            x: ENEMY_SCALE,
+            y: ENEMY_SCALE,
+        }),
+        ..default()
+    }
+}
+
+fn spawn_random_enemy(
+    commands: &mut Commands,
+    assest_server: &AssetServer,
+    arena: &Arena,
+    balance: &Balance,
+    game_rng: &mut GameRng,
+) {
+    let random_x = game_rng.random::<f32>() * arena.width;
+    let random_y = game_rng.random::<f32>() * arena.height;
+    let direction = Vec2::new(game_rng.random::<f32>(), game_rng.random::<f32>()).normalize();
+    let speed = game_rng.random_range(balance.enemy_speed.clone());
+
+    spawn_enemy(
+        commands,
+        assest_server,
+        Vec2::new(random_x, random_y),
+        direction,
+        speed,
+    );
+}
+
 pub fn enemy_movement(mut enemy_query: Query<(&mut Transform, &Enemy)>, time: Res<Time>) {
     for (mut transfrom, enemy) in enemy_query.iter_mut() {
         let direction = Vec3::new(enemy.direction.x, enemy.direction.y, 0.0);
@@ -81,86 +131,198 @@ pub fn enemy_movement(mut enemy_query: Query<(&mut Transform, &Enemy)>, time: Re
 
 pub fn update_enemy_direction(
     enemy_query: Query<(&Transform, &mut Enemy)>,
-    windows_query: Query<&Window, With<PrimaryWindow>>,
-    mut commands: Commands,
-    assest_server: Res<AssetServer>,
+    arena: Res<Arena>,
+    preferences: Res<Preferences>,
+    mut sound_event_writer: EventWriter<SoundEvent>,
+    mut particle_event_writer: EventWriter<ParticleEvent>,
 ) {
-    if let Ok(window) = windows_query.single() {
-        let half_enemy_scale = ENEMY_SCALE / 2.0;
-
-        let x_min = half_enemy_scale;
-        let x_max = window.width() - half_enemy_scale;
-        let y_min = half_enemy_scale;
-        let y_max = window.height() - half_enemy_scale;
-
-        for (transfrom, mut enemy) in enemy_query {
-            if transfrom.translation.x <= x_min || transfrom.translation.x >= x_max {
-                if PLAY_ENEMY_BOUNCE_SOUND {
-                    commands.spawn(AudioPlayer::new(assest_server.load("audio/pluck_001.ogg")));
-                }
-                enemy.direction.x *= -1.0;
+    let half_enemy_scale = ENEMY_SCALE / 2.0;
+
+    let x_min = half_enemy_scale;
+    let x_max = arena.width - half_enemy_scale;
+    let y_min = half_enemy_scale;
+    let y_max = arena.height - half_enemy_scale;
+
+    for (transfrom, mut enemy) in enemy_query {
+        // Points away from the walls hit, for the particles to spray along
+        let mut wall_normal = Vec2::ZERO;
+
+        if transfrom.translation.x <= x_min || transfrom.translation.x >= x_max {
+            if preferences.enemy_bounce_sound {
+                sound_event_writer.write(SoundEvent::EnemyBounce);
             }
-            if transfrom.translation.y <= y_min || transfrom.translation.y >= y_max {
-                if PLAY_ENEMY_BOUNCE_SOUND {
-                    commands.spawn(AudioPlayer::new(assest_server.load("audio/pluck_001.ogg")));
-                }
-                enemy.direction.y *= -1.0;
+            enemy.direction.x *= -1.0;
+            wall_normal.x = if transfrom.translation.x <= x_min {
+                1.0
+            } else {
+                -1.0
+            };
+        }
+        if transfrom.translation.y <= y_min || transfrom.translation.y >= y_max {
+            if preferences.enemy_bounce_sound {
+                sound_event_writer.write(SoundEvent::EnemyBounce);
             }
+            enemy.direction.y *= -1.0;
+            wall_normal.y = if transfrom.translation.y <= y_min {
+                1.0
+            } else {
+                -1.0
+            };
+        }
+
+        if wall_normal != Vec2::ZERO {
+            particle_event_writer.write(ParticleEvent {
+                effect: ParticleEffect::EnemyBounce,
+                position: transfrom.translation.truncate(),
+                direction: wall_normal,
+            });
         }
     }
 }
 
 pub fn confine_enemy_movement(
     mut enemy_query: Query<&mut Transform, With<Enemy>>,
-    windw_query: Query<&Window, With<PrimaryWindow>>,
+    arena: Res<Arena>,
 ) {
-    if let Ok(window) = windw_query.single() {
-        let half_enemy_scale = ENEMY_SCALE / 2.0;
-
-        let x_min = half_enemy_scale;
-        let x_max = window.width() - half_enemy_scale;
-        let y_min = half_enemy_scale;
-        let y_max = window.height() - half_enemy_scale;
-
-        for mut transform in enemy_query.iter_mut() {
-            if transform.translation.x > x_max {
-                transform.translation.x = x_max;
-            } else if transform.translation.x < x_min {
-                transform.translation.x = x_min;
-            }
-            if transform.translation.y > y_max {
-                transform.translation.y = y_max;
-            } else if transform.translation.y < y_min {
-                transform.translation.y = y_min;
-            }
+    let half_enemy_scale = ENEMY_SCALE / 2.0;
+
+    let x_min = half_enemy_scale;
+    let x_max = arena.width - half_enemy_scale;
+    let y_min = half_enemy_scale;
+    let y_max = arena.height - half_enemy_scale;
+
+    for mut transform in enemy_query.iter_mut() {
+        if transform.translation.x > x_max {
+            transform.translation.x = x_max;
+        } else if transform.translation.x < x_min {
+            transform.translation.x = x_min;
+        }
+        if transform.translation.y > y_max {
+            transform.translation.y = y_max;
+        } else if transform.translation.y < y_min {
+            transform.translation.y = y_min;
         }
     }
 }
 
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 pub fn enemy_hit_player(
     mut commands: Commands,
     mut game_over_event_writter: EventWriter<GameOver>,
-    mut player_query: Query<(Entity, &Transform), With<Player>>,
-    enemy_query: Query<&Transform, With<Enemy>>,
-    assest_server: Res<AssetServer>,
+    mut player_query: Query<
+        (
+            Entity,
+            &mut Transform,
+            &mut TranslationInterpolation,
+            &mut Lives,
+            &Dash,
+            &Shield,
+        ),
+        (With<Player>, Without<Invulnerable>),
+    >,
+    enemy_query: Query<(Entity, &Transform), (With<Enemy>, Without<Player>)>,
+    arena: Res<Arena>,
     score: Res<Score>,
+    mut sound_event_writer: EventWriter<SoundEvent>,
+    mut particle_event_writer: EventWriter<ParticleEvent>,
+    mut game_rng: ResMut<GameRng>,
 ) {
-    if let Ok((player_entity, player_transform)) = player_query.single_mut() {
-        for enemy_tranform in enemy_query {
+    if let Ok((
+        player_entity,
+        mut player_transform,
+        mut player_interpolation,
+        mut lives,
+        dash,
+        shield,
+    )) = player_query.single_mut()
+    {
+        if dash.ability.is_active() || shield.ability.is_active() {
+            return;
+        }
+
+        let player_radius = PLAYER_SCALE / 2.0;
+        let enemy_radius = ENEMY_SCALE / 2.0;
+
+        let hit = enemy_query.iter().any(|(_, enemy_tranform)| {
             let distance = player_transform
                 .translation
                 .distance(enemy_tranform.translation);
 
-            let player_radius = PLAYER_SCALE / 2.0;
-            let enemy_radius = ENEMY_SCALE / 2.0;
+            distance < player_radius + enemy_radius
+        });
 
-            if distance < player_radius + enemy_radius {
-                commands.spawn(AudioPlayer::new(
-                    assest_server.load("audio/explosionCrunch_000.ogg"),
-                ));
-                commands.entity(player_entity).despawn();
+        if !hit {
+            return;
+        }
+
+        // A respawn would otherwise cash in every near miss the hit was part of
+        for (enemy_entity, _) in enemy_query {
+            commands.entity(enemy_entity).remove::<NearMiss>();
+        }
 
-                game_over_event_writter.write(GameOver { score: score.value });
+        lives.remaining = lives.remaining.saturating_sub(1);
+
+        if lives.remaining == 0 {
+            commands.entity(player_entity).despawn();
+            sound_event_writer.write(SoundEvent::PlayerDied);
+            particle_event_writer.write(ParticleEvent {
+                effect: ParticleEffect::PlayerDied,
+                position: player_transform.translation.truncate(),
+                direction: Vec2::ZERO,
+            });
+
+            game_over_event_writter.write(GameOver { score: score.value });
+        } else {
+            let enemy_positions: Vec<Vec2> = enemy_query
+                .iter()
+                .map(|(_, enemy_transform)| enemy_transform.translation.truncate())
+                .collect();
+            let position = safe_player_position(&arena, &enemy_positions, &mut game_rng);
+
+            let translation = position.extend(player_transform.translation.z);
+            snap_translation(
+                &mut player_transform,
+                &mut player_interpolation,
+                translation,
+            );
+            commands
+                .entity(player_entity)
+                .insert(Invulnerable::default());
+            sound_event_writer.write(SoundEvent::PlayerHit);
+
+            info!(player = %player_entity, lives = lives.remaining, "Player hit");
+        }
+    }
+}
+
+pub fn enemy_near_miss_player(
+    mut commands: Commands,
+    player_query: Query<&Transform, (With<Player>, Without<Invulnerable>)>,
+    enemy_query: Query<(Entity, &Transform, Has<NearMiss>), With<Enemy>>,
+    combo: Res<Combo>,
+    mut score_event_writer: EventWriter<ScoreEvent>,
+) {
+    if let Ok(player_transform) = player_query.single() {
+        let collision_distance = PLAYER_SCALE / 2.0 + ENEMY_SCALE / 2.0;
+
+        for (enemy_entity, enemy_transform, near_miss) in enemy_query {
+            let distance = player_transform
+                .translation
+                .distance(enemy_transform.translation);
+
+            let is_close = distance < collision_distance + NEAR_MISS_DISTANCE;
+
+            if is_close && !near_miss {
+                commands.entity(enemy_entity).insert(NearMiss);
+            } else if !is_close && near_miss {
+                commands.entity(enemy_entity).remove::<NearMiss>();
+
+                score_event_writer.write(ScoreEvent {
+                    kind: ScoreKind::NearMiss,
+                    points: combo.near_miss_points(),
+                    multiplier: combo.multiplier,
+                    position: enemy_transform.translation.truncate(),
+                });
             }
         }
     }
@@ -172,33 +334,23 @@ pub fn tick_enemy_spawn_timer(mut enemy_spawn_timer: ResMut<EnemySpawnTime>, tim
 
 pub fn spawn_enemies_over_time(
     mut commands: Commands,
-    windows_query: Query<&Window, With<PrimaryWindow>>,
+    arena: Res<Arena>,
     enemy_spawn_timer: Res<EnemySpawnTime>,
     assest_server: Res<AssetServer>,
+    balance: Res<Balance>,
+    mut game_rng: ResMut<GameRng>,
+    mut sound_event_writer: EventWriter<SoundEvent>,
 ) {
     if enemy_spawn_timer.timer.finished() {
-        let window = windows_query.single().unwrap();
-
-        commands.spawn(AudioPlayer::new(assest_server.load("audio/drop_001.ogg")));
-        (0..ENEMY_SPAWN_OVER_TIME).into_iter().for_each(|_| {
-            let random_x = random::<f32>() * window.width();
-            let random_y = random::<f32>() * window.height();
-
-            commands.spawn((
-                Sprite {
-                    image: assest_server.load("sprites/ball_red_large.png"),
-                    custom_size: Some(Vec2 {
-                        x: ENEMY_SCALE,
-                        y: ENEMY_SCALE,
-                    }),
-                    ..default()
-                },
-                Transform::from_xyz(random_x, random_y, 0.0),
-                Enemy {
-                    direction: Vec2::new(random::<f32>(), random::<f32>()).normalize(),
-                    speed: rand::random_range(ENEMY_SPEED_VARIATY),
-                },
-            ));
+        sound_event_writer.write(SoundEvent::EnemiesSpawned);
+        (0..ENEMY_SPAWN_OVER_TIME).for_each(|_| {
+            spawn_random_enemy(
+                &mut commands,
+                &assest_server,
+                &arena,
+                &balance,
+                &mut game_rng,
+            );
         });
     }
 }
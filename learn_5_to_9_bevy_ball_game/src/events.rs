@@ -1,6 +1,65 @@
 use bevy::prelude::*;
+use serde::Deserialize;
 
-#[derive(Event)]
-pub(super) struct GameOver {
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameOver {
     pub score: u32,
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScoreKind {
+    Star,
+    NearMiss,
+}
+
+#[derive(Event, Debug)]
+pub struct ScoreEvent {
+    pub kind: ScoreKind,
+    pub points: u32,
+    pub multiplier: u32,
+    pub position: Vec2,
+}
+
+// Something happened that should be heard. Which clips it plays is up to the sound table.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum SoundEvent {
+    StarCollected,
+    StarsSpawned,
+    EnemyBounce,
+    EnemiesSpawned,
+    PlayerHit,
+    PlayerDied,
+    Dash,
+    Shield,
+    UiClick,
+}
+
+impl SoundEvent {
+    pub const ALL: [SoundEvent; 9] = [
+        SoundEvent::StarCollected,
+        SoundEvent::StarsSpawned,
+        SoundEvent::EnemyBounce,
+        SoundEvent::EnemiesSpawned,
+        SoundEvent::PlayerHit,
+        SoundEvent::PlayerDied,
+        SoundEvent::Dash,
+        SoundEvent::Shield,
+        SoundEvent::UiClick,
+    ];
+}
+
+// Something happened that should be seen. How it looks is up to the effect's emitter.
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct ParticleEvent {
+    pub effect: ParticleEffect,
+    pub position: Vec2,
+    // Which way the particles spray, or zero for all around
+    pub direction: Vec2,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParticleEffect {
+    StarCollected,
+    EnemyBounce,
+    PlayerDied,
+}
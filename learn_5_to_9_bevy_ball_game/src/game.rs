@@ -1,21 +1,43 @@
 use crate::imports::*;
+use bevy::asset::io::file::FileAssetReader;
+use bevy::ecs::schedule::ExecutorKind;
 use bevy::prelude::*;
-use bevy::window::PrimaryWindow;
+use std::path::PathBuf;
 
 pub struct GamePlugin;
 
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((
-            GameSetupPlugin,
-            DefaultPlugins,
+        // Running the simulation on one thread keeps the order of its systems the same
+        // from tick to tick, which replays and seeded runs depend on
+        app.edit_schedule(FixedUpdate, |schedule| {
+            schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+        })
+        .init_resource::<Balance>()
+        .add_plugins((
+            RngPlugin,
+            ReplayPlugin,
+            InterpolationPlugin,
+            PlayerInputPlugin,
             PlayerPlugin,
+            AbilityPlugin,
+            HudPlugin,
             ScorePlugin,
             StarPlugin,
             EnemyPlugin,
+            GameOverPlugin,
+            WorldScenePlugin,
+            SoundPlugin,
+            MusicPlugin,
+            ParticlePlugin,
         ))
         .init_state::<AppState>()
-        .init_state::<SimulationState>();
+        .init_state::<SimulationState>()
+        .init_state::<SettingsState>()
+        .init_resource::<Preferences>()
+        .init_resource::<Arena>()
+        .add_event::<GameOver>()
+        .add_systems(Update, handle_game_over);
     }
 }
 
@@ -23,23 +45,50 @@ pub struct GameSetupPlugin;
 
 impl Plugin for GameSetupPlugin {
     fn build(&self, app: &mut App) {
-        app.add_event::<GameOver>()
-            .add_systems(Startup, spawn_camera)
-            .add_systems(Update, (exit_game, handle_game_over));
+        app.add_plugins(ArenaCameraPlugin)
+            .add_systems(Update, exit_game);
     }
 }
 
-pub fn spawn_camera(mut commands: Commands, windows_query: Query<&Window, With<PrimaryWindow>>) {
-    let window = windows_query.single().unwrap();
-
-    commands.spawn((
-        Camera2d::default(),
-        Camera {
-            hdr: true,
-            ..default()
-        },
-        Transform::from_xyz(window.width() / 2.0, window.height() / 2.0, 0.0),
-    ));
+// The playfield, with the origin at its bottom left corner. Its size is in world units
+// and doesn't follow the window.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct Arena {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Default for Arena {
+    fn default() -> Self {
+        Self {
+            width: ARENA_WIDTH,
+            height: ARENA_HEIGHT,
+        }
+    }
+}
+
+impl Arena {
+    // Bigger than the screen, so the camera follows the player around it
+    pub fn large() -> Self {
+        Self {
+            width: LARGE_ARENA_WIDTH,
+            height: LARGE_ARENA_HEIGHT,
+        }
+    }
+
+    pub fn size(&self) -> Vec2 {
+        Vec2::new(self.width, self.height)
+    }
+
+    // How much of the arena the camera shows at once
+    pub fn view_size(&self) -> Vec2 {
+        self.size()
+            .min(Vec2::new(CAMERA_VIEW_WIDTH, CAMERA_VIEW_HEIGHT))
+    }
+
+    pub fn scrolls(&self) -> bool {
+        self.view_size() != self.size()
+    }
 }
 
 pub fn exit_game(
@@ -51,12 +100,30 @@ pub fn exit_game(
     }
 }
 
-pub fn handle_game_over(mut game_over_event_reader: EventReader<GameOver>) {
+pub fn handle_game_over(mut commands: Commands, mut game_over_event_reader: EventReader<GameOver>) {
     for event in game_over_event_reader.read() {
-        println!("Your final score is {}", event.score)
+        info!(score = event.score, "Game over");
+        commands.insert_resource(NextState::Pending(AppState::GameOver));
     }
 }
 
+// Returns the value following `name` on the command line, e.g. `--seed 42`
+pub fn cli_arg(name: &str) -> Option<String> {
+    std::env::args().skip_while(|arg| arg != name).nth(1)
+}
+
+pub fn cli_flag(name: &str) -> bool {
+    std::env::args().any(|arg| arg == name)
+}
+
+// The file the asset server would load for `path`, whichever directory the game was
+// started from
+pub fn asset_file_path(path: &str) -> PathBuf {
+    FileAssetReader::get_base_path()
+        .join(AssetPlugin::default().file_path)
+        .join(path)
+}
+
 #[derive(States, Debug, Clone, PartialEq, Eq, Hash, Default)]
 pub enum SimulationState {
     Running,
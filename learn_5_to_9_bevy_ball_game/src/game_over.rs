@@ -0,0 +1,59 @@
+use crate::imports::*;
+use bevy::prelude::*;
+
+pub struct GameOverPlugin;
+
+impl Plugin for GameOverPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::GameOver), spawn_game_over_screen)
+            .add_systems(OnExit(AppState::GameOver), despawn_game_over_screen);
+    }
+}
+
+#[derive(Component)]
+pub struct GameOverScreen;
+
+pub fn spawn_game_over_screen(mut commands: Commands, score: Res<Score>, game_rng: Res<GameRng>) {
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(MENU_SPACING),
+                ..default()
+            },
+            BackgroundColor(MENU_BACKGROUND_COLOR),
+            GameOverScreen,
+        ))
+        .with_children(|parent| {
+            for (text, font_size) in [
+                ("Game Over".to_string(), MENU_TITLE_FONT_SIZE),
+                (format!("Final score: {}", score.value), MENU_FONT_SIZE),
+                (format!("Seed: {}", game_rng.seed), MENU_FONT_SIZE),
+                (
+                    "Press G to return to the main menu".to_string(),
+                    MENU_FONT_SIZE,
+                ),
+            ] {
+                parent.spawn((
+                    Text::new(text),
+                    TextFont {
+                        font_size,
+                        ..default()
+                    },
+                ));
+            }
+        });
+}
+
+pub fn despawn_game_over_screen(
+    mut commands: Commands,
+    screen_query: Query<Entity, With<GameOverScreen>>,
+) {
+    for screen_entity in screen_query {
+        commands.entity(screen_entity).despawn();
+    }
+}
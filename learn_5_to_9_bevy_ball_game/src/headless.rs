@@ -0,0 +1,217 @@
+use crate::imports::*;
+use bevy::asset::AssetPlugin;
+use bevy::audio::AudioSource;
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin as BevyStatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+use std::ops::Range;
+
+// Everything `GamePlugin` needs to run without a window, a renderer or audio output
+pub struct HeadlessPlugin;
+
+impl Plugin for HeadlessPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            InputPlugin,
+            BevyStatesPlugin,
+        ))
+        .init_asset::<Image>()
+        .init_asset::<AudioSource>()
+        .add_systems(Startup, start_simulation);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SimulationReport {
+    pub seed: u64,
+    pub score: u32,
+    pub survived_seconds: f32,
+    pub game_over: bool,
+}
+
+pub fn headless_app() -> App {
+    headless_app_with_balance(Balance::default())
+}
+
+pub fn headless_app_with_balance(balance: Balance) -> App {
+    let mut app = App::new();
+    app.insert_resource(balance)
+        .add_plugins((HeadlessPlugin, GamePlugin));
+    app
+}
+
+pub fn start_simulation(mut commands: Commands) {
+    commands.insert_resource(NextState::Pending(AppState::Game));
+    commands.insert_resource(NextState::Pending(SimulationState::Running));
+}
+
+// Advances the app one fixed timestep per update, as fast as possible, until
+// `seconds` of game time have passed or the game is over
+pub fn simulate(app: &mut App, seconds: f32) -> SimulationReport {
+    let timestep = app.world().resource::<Time<Fixed>>().timestep();
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(timestep));
+
+    // The first update only initializes the clocks and enters the game
+    app.update();
+
+    let ticks = (seconds / timestep.as_secs_f32()).ceil() as u32;
+    for _ in 0..ticks {
+        app.update();
+
+        if *app.world().resource::<State<AppState>>().get() == AppState::GameOver {
+            break;
+        }
+    }
+
+    let world = app.world();
+    SimulationReport {
+        seed: world.resource::<GameRng>().seed,
+        score: world.resource::<Score>().value,
+        survived_seconds: world.resource::<Time<Fixed>>().elapsed_secs(),
+        game_over: *world.resource::<State<AppState>>().get() == AppState::GameOver,
+    }
+}
+
+pub fn simulate_seed(seed: u64, seconds: f32, bot: bool, balance: &Balance) -> SimulationReport {
+    let mut app = headless_app_with_balance(balance.clone());
+    app.insert_resource(GameRng::new(seed));
+    if bot {
+        app.add_plugins(BotPlugin);
+    }
+
+    simulate(&mut app, seconds)
+}
+
+// Every run gets its own app, so the seeds are spread over all cores
+pub fn simulate_seeds(
+    seeds: Range<u64>,
+    seconds: f32,
+    bot: bool,
+    balance: &Balance,
+) -> Vec<SimulationReport> {
+    let seeds: Vec<u64> = seeds.collect();
+    let threads = std::thread::available_parallelism().map_or(1, |threads| threads.get());
+    let chunk_size = seeds.len().div_ceil(threads).max(1);
+
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = seeds
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|seed| simulate_seed(*seed, seconds, bot, balance))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("a simulation panicked"))
+            .collect()
+    })
+}
+
+// Spread of one measurement over several runs
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Distribution {
+    pub min: f32,
+    pub p10: f32,
+    pub median: f32,
+    pub p90: f32,
+    pub max: f32,
+    pub mean: f32,
+}
+
+impl Distribution {
+    pub fn of(values: &[f32]) -> Self {
+        assert!(
+            !values.is_empty(),
+            "a distribution needs at least one value"
+        );
+
+        let mut sorted = values.to_vec();
+        sorted.sort_by(f32::total_cmp);
+
+        let percentile = |fraction: f32| {
+            let index = fraction * (sorted.len() - 1) as f32;
+            let (low, high) = (
+                sorted[index.floor() as usize],
+                sorted[index.ceil() as usize],
+            );
+            low + (high - low) * index.fract()
+        };
+
+        Self {
+            min: sorted[0],
+            p10: percentile(0.1),
+            median: percentile(0.5),
+            p90: percentile(0.9),
+            max: sorted[sorted.len() - 1],
+            mean: sorted.iter().sum::<f32>() / sorted.len() as f32,
+        }
+    }
+}
+
+impl std::fmt::Display for Distribution {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "min {:.1}, p10 {:.1}, median {:.1}, p90 {:.1}, max {:.1}, mean {:.1}",
+            self.min, self.p10, self.median, self.p90, self.max, self.mean
+        )
+    }
+}
+
+pub fn run_headless(seconds: f32, bot: bool) {
+    let mut app = headless_app();
+    app.add_plugins(log_plugin());
+    if bot {
+        app.add_plugins(BotPlugin);
+    }
+    let report = simulate(&mut app, seconds);
+
+    println!(
+        "Simulated {:.1}s with seed {}: score {}, {}",
+        report.survived_seconds,
+        report.seed,
+        report.score,
+        if report.game_over {
+            "game over"
+        } else {
+            "still alive"
+        }
+    );
+}
+
+// One run per seed, starting from `first_seed`
+pub fn run_headless_batch(seconds: f32, runs: u64, first_seed: u64, bot: bool) {
+    let reports = simulate_seeds(
+        first_seed..first_seed + runs,
+        seconds,
+        bot,
+        &Balance::default(),
+    );
+
+    let survived: Vec<f32> = reports
+        .iter()
+        .map(|report| report.survived_seconds)
+        .collect();
+    let scores: Vec<f32> = reports.iter().map(|report| report.score as f32).collect();
+    let game_overs = reports.iter().filter(|report| report.game_over).count();
+
+    println!(
+        "Simulated {} runs of up to {:.0}s with seeds {}..{}, {} ended in game over",
+        runs,
+        seconds,
+        first_seed,
+        first_seed + runs,
+        game_overs
+    );
+    println!("Survival time: {}", Distribution::of(&survived));
+    println!("Score: {}", Distribution::of(&scores));
+}
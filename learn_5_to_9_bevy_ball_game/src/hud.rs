@@ -0,0 +1,98 @@
+use crate::imports::*;
+use bevy::prelude::*;
+
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_hud)
+            .add_systems(Update, update_cooldown_icons);
+    }
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CooldownIcon {
+    Dash,
+    Shield,
+}
+
+#[derive(Component)]
+pub struct CooldownOverlay;
+
+pub fn spawn_hud(mut commands: Commands, assest_server: Res<AssetServer>) {
+    commands
+        .spawn(Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(HUD_MARGIN),
+            bottom: Val::Px(HUD_MARGIN),
+            column_gap: Val::Px(HUD_MARGIN),
+            ..default()
+        })
+        .with_children(|parent| {
+            for (icon, image, key) in [
+                (
+                    CooldownIcon::Dash,
+                    "sprites/ball_blue_small_alt.png",
+                    DASH_KEY,
+                ),
+                (CooldownIcon::Shield, "sprites/hole_large.png", SHIELD_KEY),
+            ] {
+                parent
+                    .spawn((
+                        Node {
+                            width: Val::Px(HUD_ICON_SIZE),
+                            height: Val::Px(HUD_ICON_SIZE),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::End,
+                            ..default()
+                        },
+                        ImageNode::new(assest_server.load(image)),
+                        icon,
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn((
+                            Node {
+                                position_type: PositionType::Absolute,
+                                left: Val::Px(0.0),
+                                bottom: Val::Px(0.0),
+                                width: Val::Percent(100.0),
+                                height: Val::Percent(0.0),
+                                ..default()
+                            },
+                            BackgroundColor(HUD_COOLDOWN_COLOR),
+                            CooldownOverlay,
+                        ));
+                        parent.spawn((
+                            Text::new(format!("{:?}", key).trim_start_matches("Key").to_string()),
+                            TextFont {
+                                font_size: HUD_FONT_SIZE,
+                                ..default()
+                            },
+                        ));
+                    });
+            }
+        });
+}
+
+pub fn update_cooldown_icons(
+    player_query: Query<(&Dash, &Shield), With<Player>>,
+    icon_query: Query<(&CooldownIcon, &Children)>,
+    mut overlay_query: Query<&mut Node, With<CooldownOverlay>>,
+) {
+    let Ok((dash, shield)) = player_query.single() else {
+        return;
+    };
+
+    for (icon, children) in icon_query {
+        let cooldown = match icon {
+            CooldownIcon::Dash => &dash.ability.cooldown,
+            CooldownIcon::Shield => &shield.ability.cooldown,
+        };
+
+        for child in children.iter() {
+            if let Ok(mut node) = overlay_query.get_mut(child) {
+                node.height = Val::Percent(cooldown.fraction_remaining() * 100.0);
+            }
+        }
+    }
+}
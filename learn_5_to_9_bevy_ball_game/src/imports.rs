@@ -1,9 +1,31 @@
-pub(super) use crate::app_states::*;
-pub(super) use crate::enemy::*;
-pub(super) use crate::events::*;
-pub(super) use crate::game::*;
-pub(super) use crate::main_menu::*;
-pub(super) use crate::player::*;
-pub(super) use crate::score::*;
-pub(super) use crate::settings::*;
-pub(super) use crate::star::*;
+pub use crate::abilities::*;
+pub use crate::app_states::*;
+pub use crate::audio::*;
+pub use crate::balance::*;
+pub use crate::bot::*;
+pub use crate::camera::*;
+pub use crate::debug_overlay::*;
+pub use crate::enemy::*;
+pub use crate::events::*;
+pub use crate::game::*;
+pub use crate::game_over::*;
+pub use crate::headless::*;
+pub use crate::hud::*;
+pub use crate::input::*;
+pub use crate::interpolation::*;
+pub use crate::logging::*;
+pub use crate::main_menu::*;
+pub use crate::minimap::*;
+pub use crate::music::*;
+pub use crate::particles::*;
+pub use crate::pause_menu::*;
+pub use crate::player::*;
+pub use crate::preferences::*;
+pub use crate::replay::*;
+pub use crate::rng::*;
+pub use crate::save::*;
+pub use crate::score::*;
+pub use crate::settings::*;
+pub use crate::settings_menu::*;
+pub use crate::star::*;
+pub use crate::world_scene::*;
@@ -0,0 +1,56 @@
+use crate::imports::*;
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+
+pub struct PlayerInputPlugin;
+
+impl Plugin for PlayerInputPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PlayerInput>()
+            .add_systems(PreUpdate, read_keyboard_input.after(InputSystem))
+            .add_systems(FixedPostUpdate, clear_player_input_actions);
+    }
+}
+
+// The actions the player can take this frame, independent of where they came from.
+// One-shot actions stay set until a fixed update has had a chance to see them.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq)]
+pub struct PlayerInput {
+    pub movement: Vec2,
+    pub sprint: bool,
+    pub dash: bool,
+    pub shield: bool,
+}
+
+pub fn read_keyboard_input(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    preferences: Res<Preferences>,
+    mut player_input: ResMut<PlayerInput>,
+) {
+    let mut movement = Vec2::ZERO;
+    let [up, left, down, right] = preferences.control_scheme.movement_keys();
+
+    if keyboard_input.any_pressed(up.iter().copied()) {
+        movement.y += 1.0;
+    }
+    if keyboard_input.any_pressed(left.iter().copied()) {
+        movement.x -= 1.0;
+    }
+    if keyboard_input.any_pressed(down.iter().copied()) {
+        movement.y -= 1.0;
+    }
+    if keyboard_input.any_pressed(right.iter().copied()) {
+        movement.x += 1.0;
+    }
+
+    player_input.movement = movement;
+    player_input.sprint =
+        keyboard_input.any_pressed(preferences.control_scheme.sprint_keys().iter().copied());
+    player_input.dash |= keyboard_input.just_pressed(DASH_KEY);
+    player_input.shield |= keyboard_input.just_pressed(SHIELD_KEY);
+}
+
+pub fn clear_player_input_actions(mut player_input: ResMut<PlayerInput>) {
+    player_input.dash = false;
+    player_input.shield = false;
+}
@@ -0,0 +1,97 @@
+use crate::imports::*;
+use bevy::ecs::component::HookContext;
+use bevy::ecs::world::DeferredWorld;
+use bevy::prelude::*;
+
+pub struct InterpolationPlugin;
+
+impl Plugin for InterpolationPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<TranslationInterpolation>()
+            .add_systems(
+                RunFixedMainLoop,
+                (
+                    restore_simulated_translation
+                        .in_set(RunFixedMainLoopSystem::BeforeFixedMainLoop),
+                    interpolate_translation.in_set(RunFixedMainLoopSystem::AfterFixedMainLoop),
+                ),
+            )
+            .add_systems(FixedFirst, store_previous_translation)
+            .add_systems(FixedLast, store_current_translation)
+            .add_systems(OnEnter(SimulationState::Paused), pause_virtual_time)
+            .add_systems(OnEnter(SimulationState::Running), unpause_virtual_time);
+    }
+}
+
+// Gameplay moves `Transform` on the fixed clock. Between fixed updates the rendered
+// translation is blended from `previous` to `current`, and the simulated value is put
+// back before the next fixed update runs.
+#[derive(Component, Reflect, Debug, Default)]
+#[reflect(Component)]
+#[component(on_add = init_translation_interpolation)]
+pub struct TranslationInterpolation {
+    pub previous: Vec3,
+    pub current: Vec3,
+}
+
+fn init_translation_interpolation(mut world: DeferredWorld, context: HookContext) {
+    let translation = world
+        .get::<Transform>(context.entity)
+        .map(|transform| transform.translation)
+        .unwrap_or_default();
+
+    if let Some(mut interpolation) = world.get_mut::<TranslationInterpolation>(context.entity) {
+        interpolation.previous = translation;
+        interpolation.current = translation;
+    }
+}
+
+// Moves an entity without the next frames drawing it sliding over from where it was
+pub fn snap_translation(
+    transform: &mut Transform,
+    interpolation: &mut TranslationInterpolation,
+    translation: Vec3,
+) {
+    transform.translation = translation;
+    interpolation.previous = translation;
+    interpolation.current = translation;
+}
+
+pub fn restore_simulated_translation(
+    mut query: Query<(&mut Transform, &TranslationInterpolation)>,
+) {
+    for (mut transform, interpolation) in query.iter_mut() {
+        transform.translation = interpolation.current;
+    }
+}
+
+pub fn store_previous_translation(mut query: Query<(&Transform, &mut TranslationInterpolation)>) {
+    for (transform, mut interpolation) in query.iter_mut() {
+        interpolation.previous = transform.translation;
+    }
+}
+
+pub fn store_current_translation(mut query: Query<(&Transform, &mut TranslationInterpolation)>) {
+    for (transform, mut interpolation) in query.iter_mut() {
+        interpolation.current = transform.translation;
+    }
+}
+
+pub fn interpolate_translation(
+    mut query: Query<(&mut Transform, &TranslationInterpolation)>,
+    fixed_time: Res<Time<Fixed>>,
+) {
+    let overstep = fixed_time.overstep_fraction();
+
+    for (mut transform, interpolation) in query.iter_mut() {
+        transform.translation = interpolation.previous.lerp(interpolation.current, overstep);
+    }
+}
+
+pub fn pause_virtual_time(mut virtual_time: ResMut<Time<Virtual>>) {
+    virtual_time.pause();
+}
+
+pub fn unpause_virtual_time(mut virtual_time: ResMut<Time<Virtual>>) {
+    virtual_time.unpause();
+}
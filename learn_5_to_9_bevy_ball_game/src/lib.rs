@@ -0,0 +1,34 @@
+mod abilities;
+mod app_states;
+mod audio;
+mod balance;
+mod bot;
+mod camera;
+mod debug_overlay;
+mod enemy;
+mod events;
+mod game;
+mod game_over;
+mod headless;
+mod hud;
+mod imports;
+mod input;
+mod interpolation;
+mod logging;
+mod main_menu;
+mod minimap;
+mod music;
+mod particles;
+mod pause_menu;
+mod player;
+mod preferences;
+mod replay;
+mod rng;
+mod save;
+mod score;
+mod settings;
+mod settings_menu;
+mod star;
+mod world_scene;
+
+pub use crate::imports::*;
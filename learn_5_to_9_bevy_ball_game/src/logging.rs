@@ -0,0 +1,37 @@
+use crate::imports::*;
+use bevy::log::{BoxedLayer, LogPlugin};
+use bevy::prelude::*;
+use std::fs::File;
+use std::sync::Mutex;
+use tracing_subscriber::Layer;
+
+// Every module logs under its own target, e.g. `learn_2_bevy_ball_game::score`.
+// RUST_LOG overrides the filter from the settings, e.g.
+// `RUST_LOG=learn_2_bevy_ball_game=info,learn_2_bevy_ball_game::score=debug`
+pub fn log_plugin() -> LogPlugin {
+    LogPlugin {
+        level: LOG_LEVEL,
+        filter: LOG_FILTER.to_string(),
+        custom_layer: json_log_layer,
+    }
+}
+
+// `--log-file <path>` also writes every event as a line of JSON, for analysing a session
+// afterwards
+fn json_log_layer(_app: &mut App) -> Option<BoxedLayer> {
+    let path = cli_arg("--log-file").or_else(|| LOG_FILE_PATH.map(String::from))?;
+
+    // Nothing is listening to log events yet
+    match File::create(&path) {
+        Ok(file) => Some(
+            tracing_subscriber::fmt::layer()
+                .json()
+                .with_writer(Mutex::new(file))
+                .boxed(),
+        ),
+        Err(error) => {
+            eprintln!("Could not create log file {}: {}", path, error);
+            None
+        }
+    }
+}
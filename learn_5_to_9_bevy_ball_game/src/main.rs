@@ -1,19 +1,46 @@
-mod app_states;
-mod enemy;
-mod events;
-mod game;
-mod imports;
-mod main_menu;
-mod player;
-mod score;
-mod settings;
-mod star;
-
-use crate::imports::*;
 use bevy::prelude::*;
+use learn_2_bevy_ball_game::*;
 
 fn main() {
-    App::new()
-        .add_plugins((GamePlugin, MainMenuPlugin, StatesPlugin))
-        .run();
+    let bot = cli_flag("--bot");
+
+    if let Some(seconds) = cli_arg("--headless") {
+        let seconds = seconds
+            .parse()
+            .expect("--headless expects a number of seconds");
+
+        match cli_arg("--runs") {
+            Some(runs) => run_headless_batch(
+                seconds,
+                runs.parse().expect("--runs expects a number of runs"),
+                seed_from_args().unwrap_or(0),
+                bot,
+            ),
+            None => run_headless(seconds, bot),
+        }
+        return;
+    }
+
+    let mut app = App::new();
+    // Inserted before the plugins so they start out with the large arena
+    if cli_flag("--large-world") {
+        app.insert_resource(Arena::large());
+    }
+    app.add_plugins((
+        DefaultPlugins.set(log_plugin()),
+        DebugOverlayPlugin,
+        GameSetupPlugin,
+        GamePlugin,
+        MainMenuPlugin,
+        MinimapPlugin,
+        PauseMenuPlugin,
+        PreferencesPlugin,
+        SavePlugin,
+        SettingsMenuPlugin,
+        StatesPlugin,
+    ));
+    if bot {
+        app.add_plugins(BotPlugin);
+    }
+    app.run();
 }
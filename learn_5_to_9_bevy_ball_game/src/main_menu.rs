@@ -1,13 +1,71 @@
+use crate::imports::*;
+use bevy::input::common_conditions::input_just_pressed;
 use bevy::prelude::*;
 
 pub struct MainMenuPlugin;
 
 impl Plugin for MainMenuPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, main_menu);
+        app.add_systems(OnEnter(AppState::MainMenu), main_menu)
+            .add_systems(OnExit(AppState::MainMenu), despawn_main_menu)
+            .add_systems(
+                Update,
+                continue_saved_game
+                    .run_if(in_state(AppState::MainMenu))
+                    .run_if(in_state(SettingsState::Closed))
+                    .run_if(input_just_pressed(KeyCode::KeyC))
+                    .run_if(can_continue_saved_game),
+            );
     }
 }
 
-pub fn main_menu() {
-    println!("You are now in the main menu");
+#[derive(Component)]
+pub struct MainMenuScreen;
+
+pub fn main_menu(mut commands: Commands, replay_recorder: Option<Res<ReplayRecorder>>) {
+    info!("You are now in the main menu");
+
+    let mut lines = vec![
+        ("Ball Game", MENU_TITLE_FONT_SIZE),
+        ("Press G to start a new game", MENU_FONT_SIZE),
+    ];
+    if can_continue_saved_game(replay_recorder) {
+        lines.push(("Press C to continue your last game", MENU_FONT_SIZE));
+    }
+    lines.push(("Press O for settings", MENU_FONT_SIZE));
+
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(MENU_SPACING),
+                ..default()
+            },
+            BackgroundColor(MENU_BACKGROUND_COLOR),
+            MainMenuScreen,
+        ))
+        .with_children(|parent| {
+            for (text, font_size) in lines {
+                parent.spawn((
+                    Text::new(text),
+                    TextFont {
+                        font_size,
+                        ..default()
+                    },
+                ));
+            }
+        });
+}
+
+pub fn despawn_main_menu(
+    mut commands: Commands,
+    screen_query: Query<Entity, With<MainMenuScreen>>,
+) {
+    for screen_entity in screen_query {
+        commands.entity(screen_entity).despawn();
+    }
 }
@@ -0,0 +1,169 @@
+use crate::imports::*;
+use bevy::platform::collections::HashSet;
+use bevy::prelude::*;
+
+// Shows where the player, the enemies and the stars are in an arena too large to see at
+// once, along with the part of it on screen
+pub struct MinimapPlugin;
+
+impl Plugin for MinimapPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_minimap.run_if(arena_scrolls))
+            .add_systems(
+                Update,
+                (show_minimap, update_minimap_dots, update_minimap_view)
+                    .run_if(any_with_component::<Minimap>),
+            );
+    }
+}
+
+#[derive(Component)]
+pub struct Minimap;
+
+#[derive(Component)]
+pub struct MinimapView;
+
+// Follows `0` around the minimap
+#[derive(Component)]
+pub struct MinimapDot(pub Entity);
+
+pub fn arena_scrolls(arena: Res<Arena>) -> bool {
+    arena.scrolls()
+}
+
+pub fn spawn_minimap(mut commands: Commands, arena: Res<Arena>) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(HUD_MARGIN),
+                bottom: Val::Px(HUD_MARGIN),
+                width: Val::Px(MINIMAP_WIDTH),
+                height: Val::Px(MINIMAP_WIDTH * arena.height / arena.width),
+                overflow: Overflow::clip(),
+                ..default()
+            },
+            BackgroundColor(MINIMAP_BACKGROUND_COLOR),
+            Visibility::Hidden,
+            Minimap,
+        ))
+        .with_child((
+            Node {
+                position_type: PositionType::Absolute,
+                ..default()
+            },
+            Outline::new(Val::Px(1.0), Val::ZERO, MINIMAP_VIEW_COLOR),
+            MinimapView,
+        ));
+}
+
+pub fn show_minimap(
+    app_state: Res<State<AppState>>,
+    mut minimap_query: Query<&mut Visibility, With<Minimap>>,
+) {
+    for mut visibility in &mut minimap_query {
+        visibility.set_if_neq(if *app_state.get() == AppState::Game {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        });
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn update_minimap_dots(
+    mut commands: Commands,
+    minimap_query: Query<Entity, With<Minimap>>,
+    mut dot_query: Query<(Entity, &MinimapDot, &mut Node)>,
+    tracked_query: Query<
+        (Entity, &Transform, Has<Player>, Has<Enemy>),
+        Or<(With<Player>, With<Enemy>, With<Star>)>,
+    >,
+    arena: Res<Arena>,
+) {
+    let Ok(minimap_entity) = minimap_query.single() else {
+        return;
+    };
+
+    // Moves the dots that still have something to follow and drops the rest
+    let mut shown = HashSet::new();
+    for (dot_entity, dot, mut node) in &mut dot_query {
+        match tracked_query.get(dot.0) {
+            Ok((_, transform, ..)) => {
+                let (left, bottom) = minimap_position(transform.translation.truncate(), &arena);
+                node.left = left;
+                node.bottom = bottom;
+                shown.insert(dot.0);
+            }
+            Err(_) => commands.entity(dot_entity).despawn(),
+        }
+    }
+
+    for (entity, transform, is_player, is_enemy) in &tracked_query {
+        if shown.contains(&entity) {
+            continue;
+        }
+
+        let (left, bottom) = minimap_position(transform.translation.truncate(), &arena);
+        let color = if is_player {
+            MINIMAP_PLAYER_COLOR
+        } else if is_enemy {
+            MINIMAP_ENEMY_COLOR
+        } else {
+            MINIMAP_STAR_COLOR
+        };
+
+        commands.entity(minimap_entity).with_child((
+            Node {
+                position_type: PositionType::Absolute,
+                left,
+                bottom,
+                width: Val::Px(MINIMAP_DOT_SIZE),
+                height: Val::Px(MINIMAP_DOT_SIZE),
+                // Centers the dot on the position instead of hanging it off a corner
+                margin: UiRect {
+                    left: Val::Px(-MINIMAP_DOT_SIZE / 2.0),
+                    bottom: Val::Px(-MINIMAP_DOT_SIZE / 2.0),
+                    ..default()
+                },
+                ..default()
+            },
+            BackgroundColor(color),
+            BorderRadius::MAX,
+            // The player is drawn over anything it overlaps
+            ZIndex(is_player as i32),
+            MinimapDot(entity),
+        ));
+    }
+}
+
+pub fn update_minimap_view(
+    camera_query: Query<&Transform, With<GameCamera>>,
+    mut view_query: Query<&mut Node, With<MinimapView>>,
+    arena: Res<Arena>,
+) {
+    let Ok(camera_transform) = camera_query.single() else {
+        return;
+    };
+    let Ok(mut node) = view_query.single_mut() else {
+        return;
+    };
+
+    let view_size = arena.view_size();
+    let corner = camera_transform.translation.truncate() - view_size / 2.0;
+    let (left, bottom) = minimap_position(corner, &arena);
+
+    node.left = left;
+    node.bottom = bottom;
+    node.width = Val::Percent(view_size.x / arena.width * 100.0);
+    node.height = Val::Percent(view_size.y / arena.height * 100.0);
+}
+
+// The offsets from the minimap's bottom left corner that match a point in the arena
+fn minimap_position(position: Vec2, arena: &Arena) -> (Val, Val) {
+    let fraction = position / arena.size();
+    (
+        Val::Percent(fraction.x * 100.0),
+        Val::Percent(fraction.y * 100.0),
+    )
+}
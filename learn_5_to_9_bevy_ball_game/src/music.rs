@@ -0,0 +1,136 @@
+use crate::imports::*;
+use bevy::audio::Volume;
+use bevy::prelude::*;
+
+pub struct MusicPlugin;
+
+impl Plugin for MusicPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MusicDuck>().add_systems(
+            Update,
+            (
+                crossfade_music.run_if(state_changed::<AppState>),
+                fade_music,
+                duck_music,
+                apply_music_volume,
+            )
+                .chain(),
+        );
+    }
+}
+
+// A looping track, faded towards `target` so a new one can come in while the old one
+// goes out
+#[derive(Component, Debug)]
+pub struct MusicTrack {
+    pub state: AppState,
+    pub level: f32,
+    pub target: f32,
+}
+
+// How loud the music is while the game is paused, relative to the music volume
+#[derive(Resource, Debug)]
+pub struct MusicDuck {
+    pub level: f32,
+}
+
+impl Default for MusicDuck {
+    fn default() -> Self {
+        Self { level: 1.0 }
+    }
+}
+
+pub fn music_for_state(state: &AppState) -> &'static str {
+    match state {
+        AppState::MainMenu => MAIN_MENU_MUSIC,
+        AppState::Game => GAME_MUSIC,
+        AppState::GameOver => GAME_OVER_MUSIC,
+    }
+}
+
+pub fn crossfade_music(
+    mut commands: Commands,
+    app_state: Res<State<AppState>>,
+    mut track_query: Query<&mut MusicTrack>,
+    assest_server: Res<AssetServer>,
+) {
+    let state = app_state.get();
+
+    let mut already_playing = false;
+    for mut track in &mut track_query {
+        // Coming back to a state before its track faded out picks that track back up
+        if track.state == *state {
+            track.target = 1.0;
+            already_playing = true;
+        } else {
+            track.target = 0.0;
+        }
+    }
+
+    if !already_playing {
+        commands.spawn((
+            AudioPlayer::new(assest_server.load(music_for_state(state))),
+            PlaybackSettings::LOOP.with_volume(Volume::SILENT),
+            AudioBus::Music,
+            MusicTrack {
+                state: state.clone(),
+                level: 0.0,
+                target: 1.0,
+            },
+        ));
+    }
+}
+
+// Fades run on real time, virtual time stops while the game is paused
+pub fn fade_music(
+    mut commands: Commands,
+    mut track_query: Query<(Entity, &mut MusicTrack)>,
+    time: Res<Time<Real>>,
+) {
+    let step = time.delta_secs() / MUSIC_CROSSFADE_TIME;
+
+    for (track_entity, mut track) in &mut track_query {
+        track.level = move_towards(track.level, track.target, step);
+
+        if track.level == 0.0 && track.target == 0.0 {
+            commands.entity(track_entity).despawn();
+        }
+    }
+}
+
+pub fn duck_music(
+    mut music_duck: ResMut<MusicDuck>,
+    app_state: Res<State<AppState>>,
+    simulation_state: Res<State<SimulationState>>,
+    time: Res<Time<Real>>,
+) {
+    let paused =
+        *app_state.get() == AppState::Game && *simulation_state.get() == SimulationState::Paused;
+    let target = if paused { MUSIC_PAUSED_LEVEL } else { 1.0 };
+
+    music_duck.level = move_towards(
+        music_duck.level,
+        target,
+        time.delta_secs() / MUSIC_DUCK_TIME,
+    );
+}
+
+pub fn apply_music_volume(
+    audio_volumes: Res<AudioVolumes>,
+    music_duck: Res<MusicDuck>,
+    mut track_query: Query<(&MusicTrack, &mut AudioSink)>,
+) {
+    let music_volume = audio_volumes.volume(AudioBus::Music) * music_duck.level;
+
+    for (track, mut sink) in &mut track_query {
+        sink.set_volume(Volume::Linear(music_volume * track.level));
+    }
+}
+
+fn move_towards(current: f32, target: f32, step: f32) -> f32 {
+    if current < target {
+        (current + step).min(target)
+    } else {
+        (current - step).max(target)
+    }
+}
@@ -0,0 +1,157 @@
+use crate::imports::*;
+use bevy::prelude::*;
+use rand::Rng;
+use rand::seq::IndexedRandom;
+use std::f32::consts::PI;
+use std::ops::Range;
+
+const PARTICLE_SPRITES: [&str; 4] = [
+    "sprites/particle_0.png",
+    "sprites/particle_1.png",
+    "sprites/particle_2.png",
+    "sprites/particle_3.png",
+];
+
+pub struct ParticlePlugin;
+
+impl Plugin for ParticlePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ParticleEvent>()
+            .add_systems(Update, (spawn_particles, update_particles).chain());
+    }
+}
+
+// How an effect's particles start out and change over their life
+pub struct ParticleEmitter {
+    pub count: usize,
+    pub color: Color,
+    pub lifetime: Range<f32>,
+    pub speed: Range<f32>,
+    // Radians either side of the event's direction
+    pub spread: f32,
+    pub size: Range<f32>,
+    // Both run from the particle's birth at 0 to its death at 1
+    pub scale: EasingCurve<f32>,
+    pub alpha: EasingCurve<f32>,
+}
+
+impl ParticleEffect {
+    pub fn emitter(self) -> ParticleEmitter {
+        match self {
+            ParticleEffect::StarCollected => ParticleEmitter {
+                count: 16,
+                color: Color::srgb(1.0, 0.9, 0.3),
+                lifetime: 0.3..0.6,
+                speed: 60.0..180.0,
+                spread: PI,
+                size: 4.0..8.0,
+                scale: EasingCurve::new(1.0, 0.0, EaseFunction::QuadraticIn),
+                alpha: EasingCurve::new(1.0, 0.0, EaseFunction::Linear),
+            },
+            ParticleEffect::EnemyBounce => ParticleEmitter {
+                count: 6,
+                color: Color::srgb(1.0, 0.5, 0.4),
+                lifetime: 0.15..0.3,
+                speed: 80.0..160.0,
+                spread: PI / 3.0,
+                size: 3.0..5.0,
+                scale: EasingCurve::new(1.0, 0.5, EaseFunction::Linear),
+                alpha: EasingCurve::new(1.0, 0.0, EaseFunction::QuadraticOut),
+            },
+            ParticleEffect::PlayerDied => ParticleEmitter {
+                count: 60,
+                color: Color::srgb(0.4, 0.7, 1.0),
+                lifetime: 0.6..1.2,
+                speed: 80.0..320.0,
+                spread: PI,
+                size: 6.0..14.0,
+                scale: EasingCurve::new(1.0, 2.0, EaseFunction::QuadraticOut),
+                alpha: EasingCurve::new(1.0, 0.0, EaseFunction::QuadraticIn),
+            },
+        }
+    }
+}
+
+#[derive(Component, Debug)]
+pub struct Particle {
+    pub effect: ParticleEffect,
+    pub velocity: Vec2,
+    pub size: f32,
+    pub age: f32,
+    pub lifetime: f32,
+}
+
+// Particles are only for show, so they draw from their own randomness and leave
+// `GameRng`, and with it replays, alone
+pub fn spawn_particles(
+    mut commands: Commands,
+    mut particle_event_reader: EventReader<ParticleEvent>,
+    particle_query: Query<(), With<Particle>>,
+    assest_server: Res<AssetServer>,
+) {
+    let mut rng = rand::rng();
+    let mut free_slots = PARTICLE_BUDGET.saturating_sub(particle_query.iter().count());
+
+    for event in particle_event_reader.read() {
+        let emitter = event.effect.emitter();
+        let count = emitter.count.min(free_slots);
+        free_slots -= count;
+
+        let heading = if event.direction == Vec2::ZERO {
+            0.0
+        } else {
+            event.direction.to_angle()
+        };
+
+        for _ in 0..count {
+            let angle = heading + rng.random_range(-emitter.spread..=emitter.spread);
+            let velocity = Vec2::from_angle(angle) * rng.random_range(emitter.speed.clone());
+            let size = rng.random_range(emitter.size.clone());
+            let sprite = PARTICLE_SPRITES.choose(&mut rng).unwrap();
+
+            commands.spawn((
+                Sprite {
+                    image: assest_server.load(*sprite),
+                    color: emitter.color,
+                    custom_size: Some(Vec2::splat(size)),
+                    ..default()
+                },
+                // Drawn over the balls
+                Transform::from_translation(event.position.extend(1.0)),
+                Particle {
+                    effect: event.effect,
+                    velocity,
+                    size,
+                    age: 0.0,
+                    lifetime: rng.random_range(emitter.lifetime.clone()),
+                },
+            ));
+        }
+    }
+}
+
+pub fn update_particles(
+    mut commands: Commands,
+    mut particle_query: Query<(Entity, &mut Particle, &mut Transform, &mut Sprite)>,
+    time: Res<Time>,
+) {
+    let delta = time.delta_secs();
+
+    for (entity, mut particle, mut transform, mut sprite) in &mut particle_query {
+        particle.age += delta;
+        if particle.age >= particle.lifetime {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        particle.velocity *= (-PARTICLE_DRAG * delta).exp();
+        transform.translation += (particle.velocity * delta).extend(0.0);
+
+        let emitter = particle.effect.emitter();
+        let life = particle.age / particle.lifetime;
+        sprite.custom_size = Some(Vec2::splat(
+            particle.size * emitter.scale.sample_clamped(life),
+        ));
+        sprite.color = emitter.color.with_alpha(emitter.alpha.sample_clamped(life));
+    }
+}
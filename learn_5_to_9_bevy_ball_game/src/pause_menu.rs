@@ -0,0 +1,67 @@
+use crate::imports::*;
+use bevy::prelude::*;
+
+pub struct PauseMenuPlugin;
+
+impl Plugin for PauseMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, show_pause_menu);
+    }
+}
+
+#[derive(Component)]
+pub struct PauseMenuScreen;
+
+// Shown whenever the game is paused, including when a run starts or is continued
+pub fn show_pause_menu(
+    mut commands: Commands,
+    app_state: Res<State<AppState>>,
+    simulation_state: Res<State<SimulationState>>,
+    screen_query: Query<Entity, With<PauseMenuScreen>>,
+) {
+    let paused =
+        *app_state.get() == AppState::Game && *simulation_state.get() == SimulationState::Paused;
+
+    match (paused, screen_query.is_empty()) {
+        (true, true) => spawn_pause_menu(&mut commands),
+        (false, false) => {
+            for screen_entity in screen_query {
+                commands.entity(screen_entity).despawn();
+            }
+        }
+        _ => {}
+    }
+}
+
+fn spawn_pause_menu(commands: &mut Commands) {
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(MENU_SPACING),
+                ..default()
+            },
+            BackgroundColor(MENU_BACKGROUND_COLOR),
+            PauseMenuScreen,
+        ))
+        .with_children(|parent| {
+            for (text, font_size) in [
+                ("Paused", MENU_TITLE_FONT_SIZE),
+                ("Press Space to play", MENU_FONT_SIZE),
+                ("Press O for settings", MENU_FONT_SIZE),
+                ("Press G to return to the main menu", MENU_FONT_SIZE),
+            ] {
+                parent.spawn((
+                    Text::new(text),
+                    TextFont {
+                        font_size,
+                        ..default()
+                    },
+                ));
+            }
+        });
+}
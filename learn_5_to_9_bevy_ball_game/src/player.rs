@@ -1,4 +1,5 @@
-use bevy::{prelude::*, window::PrimaryWindow};
+use bevy::prelude::*;
+use rand::Rng;
 
 use crate::imports::*;
 
@@ -18,88 +19,114 @@ pub enum PlayerSystemSet {
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
-        app.configure_sets(
-            Update,
-            PlayerSystemSet::Movement.before(PlayerSystemSet::Confinement),
-        )
-        .add_systems(Startup, spawn_players)
-        .add_systems(
-            Update,
-            (
-                player_movement.in_set(PlayerSystemSet::Movement),
-                confine_player_movement.in_set(PlayerSystemSet::Confinement),
-                player_hit_star,
+        app.register_type::<Player>()
+            .register_type::<Lives>()
+            .register_type::<Invulnerable>()
+            .configure_sets(
+                FixedUpdate,
+                PlayerSystemSet::Movement.before(PlayerSystemSet::Confinement),
+            )
+            .add_systems(Startup, spawn_players)
+            .add_systems(
+                FixedUpdate,
+                (
+                    player_movement.in_set(PlayerSystemSet::Movement),
+                    confine_player_movement.in_set(PlayerSystemSet::Confinement),
+                    player_hit_star,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Game))
+                    .run_if(in_state(SimulationState::Running)),
             )
-                .chain()
-                .run_if(in_state(AppState::Game))
-                .run_if(in_state(SimulationState::Running)),
-        );
+            .add_systems(
+                FixedUpdate,
+                blink_invulnerable_player
+                    .run_if(in_state(AppState::Game))
+                    .run_if(in_state(SimulationState::Running)),
+            );
     }
 }
 
-#[derive(Component)]
+#[derive(Component, Reflect, Debug, Default)]
+#[reflect(Component, Default)]
+#[require(TranslationInterpolation, Lives, Dash, Shield)]
 pub struct Player {}
 
-pub fn spawn_players(
-    mut commands: Commands,
-    windows_query: Query<&Window, With<PrimaryWindow>>,
-    assest_server: Res<AssetServer>,
-) {
-    let window = windows_query.single().unwrap();
-
-    commands.spawn((
-        Sprite {
-            image: assest_server.load("sprites/ball_blue_large.png"),
-            custom_size: Some(Vec2 {
-                x: PLAYER_SCALE,
-                y: PLAYER_SCALE,
-            }),
-            ..default()
-        },
-        Transform::from_xyz(window.width() / 2.0, window.height() / 2.0, 0.0),
-        Player {},
-    ));
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component, Default)]
+pub struct Lives {
+    pub remaining: u32,
 }
-pub fn player_movement(
-    keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut player_query: Query<&mut Transform, With<Player>>,
-    time: Res<Time>,
-) {
-    if let Ok(mut transform) = player_query.single_mut() {
-        let mut directions = Vec3::new(0.0, 0.0, 0.0);
 
-        if keyboard_input.pressed(KeyCode::ArrowUp) || keyboard_input.pressed(KeyCode::KeyW) {
-            if LOG_PLAYER_MOVEMENT {
-                println!("W/UP");
-            }
-            directions += Vec3::new(0.0, 1.0, 0.0);
-        }
-        if keyboard_input.pressed(KeyCode::ArrowLeft) || keyboard_input.pressed(KeyCode::KeyA) {
-            if LOG_PLAYER_MOVEMENT {
-                println!("A/LEFT");
-            }
-            directions += Vec3::new(-1.0, 0.0, 0.0);
-        }
-        if keyboard_input.pressed(KeyCode::ArrowDown) || keyboard_input.pressed(KeyCode::KeyS) {
-            if LOG_PLAYER_MOVEMENT {
-                println!("S/DOWN");
-            }
-            directions += Vec3::new(0.0, -1.0, 0.0);
+impl Default for Lives {
+    fn default() -> Self {
+        Self {
+            remaining: PLAYER_LIVES,
         }
-        if keyboard_input.pressed(KeyCode::ArrowRight) || keyboard_input.pressed(KeyCode::KeyD) {
-            if LOG_PLAYER_MOVEMENT {
-                println!("D/RIGHT");
-            }
-            directions += Vec3::new(1.0, 0.0, 0.0);
+    }
+}
+
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component, Default)]
+pub struct Invulnerable {
+    pub timer: Timer,
+}
+
+impl Default for Invulnerable {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(PLAYER_INVULNERABILITY_TIME, TimerMode::Once),
         }
-        if directions.length() > 0.0 {
-            directions = directions.normalize();
+    }
+}
+
+pub fn spawn_players(mut commands: Commands, arena: Res<Arena>, assest_server: Res<AssetServer>) {
+    spawn_player(
+        &mut commands,
+        &assest_server,
+        Vec2::new(arena.width / 2.0, arena.height / 2.0),
+    );
+}
+
+pub fn spawn_player(
+    commands: &mut Commands,
+    assest_server: &AssetServer,
+    position: Vec2,
+) -> Entity {
+    commands
+        .spawn((
+            player_sprite(assest_server),
+            Transform::from_xyz(position.x, position.y, 0.0),
+            Player {},
+        ))
+        .id()
+}
+
+pub fn player_sprite(assest_server: &AssetServer) -> Sprite {
+    Sprite {
+        image: assest_server.load("sprites/ball_blue_large.png"),
+        custom_size: Some(Vec2 {
+            x: PLAYER_SCALE,
+            y: PLAYER_SCALE,
+        }),
+        ..default()
+    }
+}
+pub fn player_movement(
+    player_input: Res<PlayerInput>,
+    mut player_query: Query<(&mut Transform, Option<&Dash>), With<Player>>,
+    time: Res<Time>,
+) {
+    if let Ok((mut transform, dash)) = player_query.single_mut() {
+        if let Some(dash) = dash.filter(|dash| dash.ability.is_active()) {
+            transform.translation += dash.direction.extend(0.0) * DASH_SPEED * time.delta_secs();
+            return;
         }
-        if keyboard_input.pressed(KeyCode::ShiftLeft) {
-            if LOG_PLAYER_MOVEMENT {
-                println!("SHIFT");
-            }
-            directions = directions + directions;
+
+        let mut directions = player_input.movement.normalize_or_zero().extend(0.0);
+
+        if player_input.sprint {
+            directions *= SPRINT_MULTIPLIER;
         }
 
         transform.translation += directions * PLAYER_SPEED * time.delta_secs();
@@ -107,17 +134,15 @@ pub fn player_movement(
 }
 pub fn confine_player_movement(
     mut player_query: Query<&mut Transform, With<Player>>,
-    windows_query: Query<&Window, With<PrimaryWindow>>,
+    arena: Res<Arena>,
 ) {
     if let Ok(mut player_transform) = player_query.single_mut() {
-        let window = windows_query.single().unwrap();
-
         let half_player_size = PLAYER_SCALE / 2.0;
 
         let x_min = half_player_size;
-        let x_max = window.width() - half_player_size;
+        let x_max = arena.width - half_player_size;
         let y_min = half_player_size;
-        let y_max = window.height() - half_player_size;
+        let y_max = arena.height - half_player_size;
 
         let mut translation = player_transform.translation;
 
@@ -139,10 +164,12 @@ pub fn confine_player_movement(
 }
 pub fn player_hit_star(
     mut commands: Commands,
-    assest_server: Res<AssetServer>,
     star_query: Query<(Entity, &Transform), With<Star>>,
     player_query: Query<&Transform, With<Player>>,
-    mut score: ResMut<Score>,
+    mut combo: ResMut<Combo>,
+    mut score_event_writer: EventWriter<ScoreEvent>,
+    mut sound_event_writer: EventWriter<SoundEvent>,
+    mut particle_event_writer: EventWriter<ParticleEvent>,
 ) {
     if let Ok(player_transform) = player_query.single() {
         for (star_entity, star_transform) in star_query {
@@ -154,15 +181,72 @@ pub fn player_hit_star(
             let star_radius = STAR_SCALE / 2.0;
 
             if distance < player_radius + star_radius {
-                if LOG_COLLECTING_STARS {
-                    println!("Player hit star");
-                }
-                score.value += 1;
-                commands.spawn(AudioPlayer::new(
-                    assest_server.load("audio/laserLarge_000.ogg"),
-                ));
+                debug!(star = %star_entity, "Player hit star");
+                score_event_writer.write(ScoreEvent {
+                    kind: ScoreKind::Star,
+                    points: combo.register_star(),
+                    multiplier: combo.multiplier,
+                    position: star_transform.translation.truncate(),
+                });
+                sound_event_writer.write(SoundEvent::StarCollected);
+                particle_event_writer.write(ParticleEvent {
+                    effect: ParticleEffect::StarCollected,
+                    position: star_transform.translation.truncate(),
+                    direction: Vec2::ZERO,
+                });
                 commands.entity(star_entity).despawn();
             }
         }
     }
 }
+
+pub fn blink_invulnerable_player(
+    mut commands: Commands,
+    mut player_query: Query<(Entity, &mut Invulnerable, &mut Visibility), With<Player>>,
+    time: Res<Time>,
+) {
+    if let Ok((player_entity, mut invulnerable, mut visibility)) = player_query.single_mut() {
+        invulnerable.timer.tick(time.delta());
+
+        if invulnerable.timer.finished() {
+            commands.entity(player_entity).remove::<Invulnerable>();
+            visibility.set_if_neq(Visibility::Inherited);
+            return;
+        }
+
+        let blinks = (invulnerable.timer.elapsed_secs() / PLAYER_BLINK_INTERVAL) as u32;
+        let new_visibility = if blinks.is_multiple_of(2) {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        };
+
+        visibility.set_if_neq(new_visibility);
+    }
+}
+
+// Tries a few random spots and keeps the one furthest away from the closest enemy
+pub fn safe_player_position(
+    arena: &Arena,
+    enemy_positions: &[Vec2],
+    game_rng: &mut GameRng,
+) -> Vec2 {
+    let half_player_size = PLAYER_SCALE / 2.0;
+    let mut random_position = || {
+        Vec2::new(
+            half_player_size + game_rng.random::<f32>() * (arena.width - PLAYER_SCALE),
+            half_player_size + game_rng.random::<f32>() * (arena.height - PLAYER_SCALE),
+        )
+    };
+    let distance_to_closest_enemy = |position: Vec2| {
+        enemy_positions
+            .iter()
+            .map(|enemy_position| enemy_position.distance(position))
+            .fold(f32::INFINITY, f32::min)
+    };
+
+    (0..PLAYER_RESPAWN_CANDIDATES.max(1))
+        .map(|_| random_position())
+        .max_by(|a, b| distance_to_closest_enemy(*a).total_cmp(&distance_to_closest_enemy(*b)))
+        .unwrap()
+}
@@ -0,0 +1,194 @@
+use crate::imports::*;
+use bevy::prelude::*;
+use bevy::window::{MonitorSelection, PresentMode, PrimaryWindow, VideoModeSelection, WindowMode};
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::Path;
+
+// Loads the player's preferences and keeps the game in line with them. Headless apps
+// leave it out and play with the defaults.
+pub struct PreferencesPlugin;
+
+impl Plugin for PreferencesPlugin {
+    fn build(&self, app: &mut App) {
+        let preferences = if Path::new(PREFERENCES_FILE_PATH).exists() {
+            Preferences::load(PREFERENCES_FILE_PATH).unwrap_or_else(|error| {
+                warn!(path = PREFERENCES_FILE_PATH, %error, "Could not load preferences");
+                Preferences::default()
+            })
+        } else {
+            Preferences::default()
+        };
+
+        app.insert_resource(preferences).add_systems(
+            Update,
+            (apply_audio_preferences, apply_window_preferences)
+                .run_if(resource_changed::<Preferences>),
+        );
+    }
+}
+
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Preferences {
+    pub master_volume: f32,
+    pub sfx_volume: f32,
+    pub music_volume: f32,
+    pub ui_volume: f32,
+    pub window_mode: PreferredWindowMode,
+    pub resolution: [u32; 2],
+    pub vsync: bool,
+    pub control_scheme: ControlScheme,
+    pub enemy_bounce_sound: bool,
+    pub score_popups: bool,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        let volumes = AudioVolumes::default();
+
+        Self {
+            master_volume: volumes.master,
+            sfx_volume: volumes.sfx,
+            music_volume: volumes.music,
+            ui_volume: volumes.ui,
+            window_mode: PreferredWindowMode::Windowed,
+            resolution: WINDOW_RESOLUTIONS[0],
+            vsync: true,
+            control_scheme: ControlScheme::WasdAndArrows,
+            enemy_bounce_sound: true,
+            score_popups: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PreferredWindowMode {
+    Windowed,
+    BorderlessFullscreen,
+    Fullscreen,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ControlScheme {
+    WasdAndArrows,
+    Wasd,
+    Arrows,
+}
+
+const WASD_KEYS: [&[KeyCode]; 4] = [
+    &[KeyCode::KeyW],
+    &[KeyCode::KeyA],
+    &[KeyCode::KeyS],
+    &[KeyCode::KeyD],
+];
+const ARROW_KEYS: [&[KeyCode]; 4] = [
+    &[KeyCode::ArrowUp],
+    &[KeyCode::ArrowLeft],
+    &[KeyCode::ArrowDown],
+    &[KeyCode::ArrowRight],
+];
+const WASD_AND_ARROW_KEYS: [&[KeyCode]; 4] = [
+    &[KeyCode::KeyW, KeyCode::ArrowUp],
+    &[KeyCode::KeyA, KeyCode::ArrowLeft],
+    &[KeyCode::KeyS, KeyCode::ArrowDown],
+    &[KeyCode::KeyD, KeyCode::ArrowRight],
+];
+
+impl ControlScheme {
+    // The keys for up, left, down and right
+    pub fn movement_keys(self) -> [&'static [KeyCode]; 4] {
+        match self {
+            ControlScheme::WasdAndArrows => WASD_AND_ARROW_KEYS,
+            ControlScheme::Wasd => WASD_KEYS,
+            ControlScheme::Arrows => ARROW_KEYS,
+        }
+    }
+
+    // The shift key on the side of the keyboard the scheme is played on
+    pub fn sprint_keys(self) -> &'static [KeyCode] {
+        match self {
+            ControlScheme::WasdAndArrows => &[KeyCode::ShiftLeft, KeyCode::ShiftRight],
+            ControlScheme::Wasd => &[KeyCode::ShiftLeft],
+            ControlScheme::Arrows => &[KeyCode::ShiftRight],
+        }
+    }
+}
+
+impl Preferences {
+    pub fn audio_volumes(&self) -> AudioVolumes {
+        AudioVolumes {
+            master: self.master_volume,
+            sfx: self.sfx_volume,
+            music: self.music_volume,
+            ui: self.ui_volume,
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let text = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(io::Error::other)?;
+        std::fs::write(path, text)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        ron::from_str(&std::fs::read_to_string(path)?)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+}
+
+pub fn save_preferences(preferences: Res<Preferences>) {
+    match preferences.save(PREFERENCES_FILE_PATH) {
+        Ok(()) => info!(path = PREFERENCES_FILE_PATH, "Saved preferences"),
+        Err(error) => error!(path = PREFERENCES_FILE_PATH, %error, "Could not save preferences"),
+    }
+}
+
+pub fn apply_audio_preferences(preferences: Res<Preferences>, mut commands: Commands) {
+    commands.insert_resource(preferences.audio_volumes());
+}
+
+// Only touches what changed, so a volume change doesn't undo the player resizing the
+// window or leaving fullscreen
+pub fn apply_window_preferences(
+    preferences: Res<Preferences>,
+    mut applied: Local<Option<Preferences>>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let Ok(mut window) = window_query.single_mut() else {
+        return;
+    };
+    let previous = applied.replace(preferences.clone());
+
+    if previous
+        .as_ref()
+        .is_none_or(|previous| previous.window_mode != preferences.window_mode)
+    {
+        window.mode = match preferences.window_mode {
+            PreferredWindowMode::Windowed => WindowMode::Windowed,
+            PreferredWindowMode::BorderlessFullscreen => {
+                WindowMode::BorderlessFullscreen(MonitorSelection::Current)
+            }
+            PreferredWindowMode::Fullscreen => {
+                WindowMode::Fullscreen(MonitorSelection::Current, VideoModeSelection::Current)
+            }
+        };
+    }
+    if previous
+        .as_ref()
+        .is_none_or(|previous| previous.resolution != preferences.resolution)
+    {
+        let [width, height] = preferences.resolution;
+        window.resolution.set(width as f32, height as f32);
+    }
+    if previous
+        .as_ref()
+        .is_none_or(|previous| previous.vsync != preferences.vsync)
+    {
+        window.present_mode = if preferences.vsync {
+            PresentMode::AutoVsync
+        } else {
+            PresentMode::AutoNoVsync
+        };
+    }
+}
@@ -0,0 +1,224 @@
+use crate::imports::*;
+use bevy::prelude::*;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const REPLAY_MAGIC: &[u8; 4] = b"BBRP";
+const REPLAY_VERSION: u8 = 3;
+const REPLAY_HEADER_SIZE: usize = 4 + 1 + 8 + 4 + 4;
+
+const INPUT_UP: u8 = 1 << 0;
+const INPUT_DOWN: u8 = 1 << 1;
+const INPUT_LEFT: u8 = 1 << 2;
+const INPUT_RIGHT: u8 = 1 << 3;
+const INPUT_SPRINT: u8 = 1 << 4;
+const INPUT_DASH: u8 = 1 << 5;
+const INPUT_SHIELD: u8 = 1 << 6;
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        if let Some(path) = cli_arg("--replay") {
+            let replay = Replay::load(&path)
+                .unwrap_or_else(|error| panic!("Could not load replay {}: {}", path, error));
+
+            info!(ticks = replay.frames.len(), path, "Replaying");
+
+            // Inserted while the plugins are still being built, so everything spawns
+            // into the arena the run was recorded in
+            app.insert_resource(GameRng::new(replay.seed))
+                .insert_resource(replay.arena)
+                .insert_resource(ReplayPlayer {
+                    replay,
+                    next_frame: 0,
+                })
+                .add_systems(Startup, start_replay)
+                .add_systems(
+                    FixedPreUpdate,
+                    inject_replay_input
+                        .run_if(in_state(AppState::Game))
+                        .run_if(in_state(SimulationState::Running)),
+                );
+        } else if let Some(path) = cli_arg("--record") {
+            app.insert_resource(ReplayRecorder {
+                path: PathBuf::from(path),
+                frames: Vec::new(),
+            })
+            .add_systems(
+                FixedPreUpdate,
+                record_player_input
+                    .run_if(in_state(AppState::Game))
+                    .run_if(in_state(SimulationState::Running)),
+            )
+            .add_systems(OnExit(AppState::Game), save_recording)
+            .add_systems(Last, save_recording.run_if(on_event::<AppExit>));
+        }
+    }
+}
+
+// One entry per fixed update the simulation ran for
+#[derive(Debug, Clone, PartialEq)]
+pub struct Replay {
+    pub seed: u64,
+    pub arena: Arena,
+    pub frames: Vec<PlayerInput>,
+}
+
+impl Replay {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(REPLAY_HEADER_SIZE + self.frames.len());
+
+        bytes.extend_from_slice(REPLAY_MAGIC);
+        bytes.push(REPLAY_VERSION);
+        bytes.extend_from_slice(&self.seed.to_le_bytes());
+        bytes.extend_from_slice(&self.arena.width.to_le_bytes());
+        bytes.extend_from_slice(&self.arena.height.to_le_bytes());
+
+        bytes.extend(self.frames.iter().map(input_to_flags));
+
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> io::Result<Self> {
+        let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message);
+
+        if bytes.len() < REPLAY_HEADER_SIZE || &bytes[0..4] != REPLAY_MAGIC {
+            return Err(invalid("not a replay file"));
+        }
+        if bytes[4] != REPLAY_VERSION {
+            return Err(invalid("unsupported replay version"));
+        }
+
+        let seed = u64::from_le_bytes(bytes[5..13].try_into().unwrap());
+        let arena = Arena {
+            width: f32::from_le_bytes(bytes[13..17].try_into().unwrap()),
+            height: f32::from_le_bytes(bytes[17..21].try_into().unwrap()),
+        };
+        let frames = bytes[REPLAY_HEADER_SIZE..]
+            .iter()
+            .map(|flags| flags_to_input(*flags))
+            .collect();
+
+        Ok(Self {
+            seed,
+            arena,
+            frames,
+        })
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        std::fs::write(path, self.to_bytes())
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+}
+
+fn input_to_flags(input: &PlayerInput) -> u8 {
+    let mut flags = 0;
+
+    if input.movement.y > 0.0 {
+        flags |= INPUT_UP;
+    }
+    if input.movement.y < 0.0 {
+        flags |= INPUT_DOWN;
+    }
+    if input.movement.x < 0.0 {
+        flags |= INPUT_LEFT;
+    }
+    if input.movement.x > 0.0 {
+        flags |= INPUT_RIGHT;
+    }
+    if input.sprint {
+        flags |= INPUT_SPRINT;
+    }
+    if input.dash {
+        flags |= INPUT_DASH;
+    }
+    if input.shield {
+        flags |= INPUT_SHIELD;
+    }
+
+    flags
+}
+
+fn flags_to_input(flags: u8) -> PlayerInput {
+    let axis = |positive: u8, negative: u8| {
+        (flags & positive != 0) as i8 as f32 - (flags & negative != 0) as i8 as f32
+    };
+
+    PlayerInput {
+        movement: Vec2::new(axis(INPUT_RIGHT, INPUT_LEFT), axis(INPUT_UP, INPUT_DOWN)),
+        sprint: flags & INPUT_SPRINT != 0,
+        dash: flags & INPUT_DASH != 0,
+        shield: flags & INPUT_SHIELD != 0,
+    }
+}
+
+#[derive(Resource)]
+pub struct ReplayRecorder {
+    pub path: PathBuf,
+    pub frames: Vec<PlayerInput>,
+}
+
+#[derive(Resource)]
+pub struct ReplayPlayer {
+    pub replay: Replay,
+    pub next_frame: usize,
+}
+
+pub fn record_player_input(mut recorder: ResMut<ReplayRecorder>, player_input: Res<PlayerInput>) {
+    recorder.frames.push(*player_input);
+}
+
+pub fn save_recording(recorder: Res<ReplayRecorder>, game_rng: Res<GameRng>, arena: Res<Arena>) {
+    let replay = Replay {
+        seed: game_rng.seed,
+        arena: *arena,
+        frames: recorder.frames.clone(),
+    };
+
+    match replay.save(&recorder.path) {
+        Ok(()) => info!(
+            ticks = replay.frames.len(),
+            path = %recorder.path.display(),
+            "Saved replay"
+        ),
+        Err(error) => error!(
+            path = %recorder.path.display(),
+            %error,
+            "Could not save replay"
+        ),
+    }
+}
+
+pub fn start_replay(mut commands: Commands) {
+    commands.insert_resource(NextState::Pending(AppState::Game));
+    commands.insert_resource(NextState::Pending(SimulationState::Running));
+}
+
+pub fn inject_replay_input(
+    mut commands: Commands,
+    mut replay_player: ResMut<ReplayPlayer>,
+    mut player_input: ResMut<PlayerInput>,
+    score: Res<Score>,
+) {
+    let next_frame = replay_player.next_frame;
+
+    match replay_player.replay.frames.get(next_frame) {
+        Some(input) => {
+            *player_input = *input;
+            replay_player.next_frame += 1;
+        }
+        None if next_frame == replay_player.replay.frames.len() => {
+            *player_input = PlayerInput::default();
+            replay_player.next_frame += 1;
+
+            commands.insert_resource(NextState::Pending(SimulationState::Paused));
+            info!(score = score.value, "Replay finished");
+        }
+        None => *player_input = PlayerInput::default(),
+    }
+}
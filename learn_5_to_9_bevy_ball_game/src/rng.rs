@@ -0,0 +1,69 @@
+use crate::imports::*;
+use bevy::prelude::*;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+pub struct RngPlugin;
+
+impl Plugin for RngPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameRng>()
+            .add_systems(Startup, print_seed);
+    }
+}
+
+// Every random decision in the game goes through this, so a seed reproduces a run
+#[derive(Resource)]
+pub struct GameRng {
+    pub seed: u64,
+    rng: ChaCha8Rng,
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: ChaCha8Rng::seed_from_u64(seed),
+        }
+    }
+
+    // How far into the seed's stream the game has drawn, so a saved run can pick up
+    // where it left off
+    pub fn position(&self) -> u128 {
+        self.rng.get_word_pos()
+    }
+
+    pub fn from_position(seed: u64, position: u128) -> Self {
+        let mut game_rng = Self::new(seed);
+        game_rng.rng.set_word_pos(position);
+        game_rng
+    }
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self::new(seed_from_args().or(RNG_SEED).unwrap_or_else(rand::random))
+    }
+}
+
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        self.rng.fill_bytes(dst)
+    }
+}
+
+pub fn seed_from_args() -> Option<u64> {
+    cli_arg("--seed").and_then(|seed| seed.parse().ok())
+}
+
+pub fn print_seed(game_rng: Res<GameRng>) {
+    info!(seed = game_rng.seed, "Seeded the game");
+}
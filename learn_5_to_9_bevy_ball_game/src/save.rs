@@ -0,0 +1,342 @@
+use crate::imports::*;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+const SAVE_VERSION: u32 = 2;
+
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(SimulationState::Paused),
+            save_current_game
+                .run_if(in_state(AppState::Game))
+                .run_if(not_replaying),
+        )
+        .add_systems(
+            Last,
+            save_current_game
+                .run_if(on_event::<AppExit>)
+                .run_if(in_state(AppState::Game))
+                .run_if(not_replaying),
+        )
+        .add_systems(
+            OnEnter(AppState::GameOver),
+            delete_saved_game.run_if(not_replaying),
+        );
+    }
+}
+
+// Everything needed to pick a run back up exactly where it was left. Timers are stored
+// as their elapsed time, their durations come from the settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SaveGame {
+    pub version: u32,
+    // The saved positions only make sense in an arena of this size
+    pub arena: [f32; 2],
+    pub seed: u64,
+    pub rng_position: u64,
+    pub score: u32,
+    pub combo: SavedCombo,
+    pub star_spawn_elapsed: Duration,
+    pub enemy_spawn_elapsed: Duration,
+    pub player: Option<SavedPlayer>,
+    pub enemies: Vec<SavedEnemy>,
+    pub stars: Vec<SavedStar>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedCombo {
+    pub multiplier: u32,
+    pub window_elapsed: Duration,
+    pub decay_elapsed: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedPlayer {
+    pub position: [f32; 2],
+    pub lives: u32,
+    pub invulnerable_elapsed: Option<Duration>,
+    pub dash: SavedAbility,
+    pub dash_direction: [f32; 2],
+    pub shield: SavedAbility,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedAbility {
+    pub duration_elapsed: Duration,
+    pub cooldown_elapsed: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedEnemy {
+    pub position: [f32; 2],
+    pub direction: [f32; 2],
+    pub speed: f32,
+    pub near_miss: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedStar {
+    pub position: [f32; 2],
+    pub lifetime_elapsed: Duration,
+}
+
+impl SaveGame {
+    pub fn capture(world: &mut World) -> Self {
+        let game_rng = world.resource::<GameRng>();
+        let (seed, rng_position) = (game_rng.seed, game_rng.position());
+        let combo = world.resource::<Combo>();
+        let combo = SavedCombo {
+            multiplier: combo.multiplier,
+            window_elapsed: combo.window.elapsed(),
+            decay_elapsed: combo.decay.elapsed(),
+        };
+
+        let player = world
+            .query_filtered::<(
+                &TranslationInterpolation,
+                &Lives,
+                Option<&Invulnerable>,
+                &Dash,
+                &Shield,
+            ), With<Player>>()
+            .iter(world)
+            .next()
+            .map(
+                |(interpolation, lives, invulnerable, dash, shield)| SavedPlayer {
+                    position: interpolation.current.truncate().to_array(),
+                    lives: lives.remaining,
+                    invulnerable_elapsed: invulnerable
+                        .map(|invulnerable| invulnerable.timer.elapsed()),
+                    dash: SavedAbility::capture(&dash.ability),
+                    dash_direction: dash.direction.to_array(),
+                    shield: SavedAbility::capture(&shield.ability),
+                },
+            );
+
+        let enemies = world
+            .query::<(&TranslationInterpolation, &Enemy, Has<NearMiss>)>()
+            .iter(world)
+            .map(|(interpolation, enemy, near_miss)| SavedEnemy {
+                position: interpolation.current.truncate().to_array(),
+                direction: enemy.direction.to_array(),
+                speed: enemy.speed,
+                near_miss,
+            })
+            .collect();
+
+        let stars = world
+            .query::<(&TranslationInterpolation, &Star)>()
+            .iter(world)
+            .map(|(interpolation, star)| SavedStar {
+                position: interpolation.current.truncate().to_array(),
+                lifetime_elapsed: star.lifetime.elapsed(),
+            })
+            .collect();
+
+        Self {
+            version: SAVE_VERSION,
+            arena: world.resource::<Arena>().size().to_array(),
+            seed,
+            rng_position: u64::try_from(rng_position).expect("rng position overflowed"),
+            score: world.resource::<Score>().value,
+            combo,
+            star_spawn_elapsed: world.resource::<StarSpawnTimer>().timer.elapsed(),
+            enemy_spawn_elapsed: world.resource::<EnemySpawnTime>().timer.elapsed(),
+            player,
+            enemies,
+            stars,
+        }
+    }
+
+    // Replaces whatever is in the arena with the saved run
+    pub fn restore(&self, world: &mut World) {
+        let entities: Vec<Entity> = world
+            .query_filtered::<Entity, Or<(With<Player>, With<Enemy>, With<Star>)>>()
+            .iter(world)
+            .collect();
+        for entity in entities {
+            world.despawn(entity);
+        }
+
+        world.insert_resource(GameRng::from_position(self.seed, self.rng_position as u128));
+        world.resource_mut::<Score>().value = self.score;
+
+        let mut combo = world.resource_mut::<Combo>();
+        combo.multiplier = self.combo.multiplier;
+        restore_timer(&mut combo.window, self.combo.window_elapsed);
+        restore_timer(&mut combo.decay, self.combo.decay_elapsed);
+
+        restore_timer(
+            &mut world.resource_mut::<StarSpawnTimer>().timer,
+            self.star_spawn_elapsed,
+        );
+        restore_timer(
+            &mut world.resource_mut::<EnemySpawnTime>().timer,
+            self.enemy_spawn_elapsed,
+        );
+
+        let assest_server = world.resource::<AssetServer>().clone();
+        let mut commands = world.commands();
+
+        if let Some(saved_player) = &self.player {
+            let player_entity = spawn_player(
+                &mut commands,
+                &assest_server,
+                Vec2::from_array(saved_player.position),
+            );
+
+            let mut dash = Dash {
+                direction: Vec2::from_array(saved_player.dash_direction),
+                ..default()
+            };
+            saved_player.dash.restore(&mut dash.ability);
+            let mut shield = Shield::default();
+            saved_player.shield.restore(&mut shield.ability);
+
+            let mut player = commands.entity(player_entity);
+            player.insert((
+                Lives {
+                    remaining: saved_player.lives,
+                },
+                dash,
+                shield,
+            ));
+            if let Some(elapsed) = saved_player.invulnerable_elapsed {
+                let mut invulnerable = Invulnerable::default();
+                restore_timer(&mut invulnerable.timer, elapsed);
+                player.insert(invulnerable);
+            }
+        }
+
+        for saved_enemy in &self.enemies {
+            let enemy_entity = spawn_enemy(
+                &mut commands,
+                &assest_server,
+                Vec2::from_array(saved_enemy.position),
+                Vec2::from_array(saved_enemy.direction),
+                saved_enemy.speed,
+            );
+            if saved_enemy.near_miss {
+                commands.entity(enemy_entity).insert(NearMiss);
+            }
+        }
+
+        for saved_star in &self.stars {
+            let star_entity = spawn_star(
+                &mut commands,
+                &assest_server,
+                Vec2::from_array(saved_star.position),
+            );
+            let mut star = Star::default();
+            restore_timer(&mut star.lifetime, saved_star.lifetime_elapsed);
+            commands.entity(star_entity).insert(star);
+        }
+
+        world.flush();
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let text = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(io::Error::other)?;
+        std::fs::write(path, text)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let save_game: Self = ron::from_str(&std::fs::read_to_string(path)?)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+
+        if save_game.version != SAVE_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "unsupported save version",
+            ));
+        }
+        Ok(save_game)
+    }
+}
+
+impl SavedAbility {
+    fn capture(ability: &Ability) -> Self {
+        Self {
+            duration_elapsed: ability.duration.elapsed(),
+            cooldown_elapsed: ability.cooldown.elapsed(),
+        }
+    }
+
+    fn restore(&self, ability: &mut Ability) {
+        restore_timer(&mut ability.duration, self.duration_elapsed);
+        restore_timer(&mut ability.cooldown, self.cooldown_elapsed);
+    }
+}
+
+// Ticking a fresh timer, rather than setting its elapsed time, also brings its finished
+// state along
+fn restore_timer(timer: &mut Timer, elapsed: Duration) {
+    timer.reset();
+    timer.tick(elapsed);
+}
+
+pub fn has_saved_game() -> bool {
+    Path::new(SAVE_FILE_PATH).exists()
+}
+
+// Runs that are being recorded or played back leave the player's own save alone
+pub fn not_replaying(
+    replay_player: Option<Res<ReplayPlayer>>,
+    replay_recorder: Option<Res<ReplayRecorder>>,
+) -> bool {
+    replay_player.is_none() && replay_recorder.is_none()
+}
+
+// A recording only holds the inputs from its seed on, so a continued run could never
+// be replayed
+pub fn can_continue_saved_game(replay_recorder: Option<Res<ReplayRecorder>>) -> bool {
+    replay_recorder.is_none() && has_saved_game()
+}
+
+pub fn save_current_game(world: &mut World) {
+    let save_game = SaveGame::capture(world);
+
+    match save_game.save(SAVE_FILE_PATH) {
+        Ok(()) => info!(path = SAVE_FILE_PATH, "Saved the game"),
+        Err(error) => error!(path = SAVE_FILE_PATH, %error, "Could not save the game"),
+    }
+}
+
+// Puts the saved run in place and enters the game paused, so the player can get ready
+pub fn continue_saved_game(world: &mut World) {
+    match SaveGame::load(SAVE_FILE_PATH) {
+        // Started with or without --large-world unlike the saved run, which would leave
+        // everything placed against the wrong walls
+        Ok(save_game) if save_game.arena != world.resource::<Arena>().size().to_array() => {
+            warn!(
+                path = SAVE_FILE_PATH,
+                saved_arena = ?save_game.arena,
+                "The saved game was played in a different arena"
+            );
+        }
+        Ok(save_game) => {
+            save_game.restore(world);
+            world.insert_resource(NextState::Pending(AppState::Game));
+            world.insert_resource(NextState::Pending(SimulationState::Paused));
+            world.send_event(SoundEvent::UiClick);
+            info!(path = SAVE_FILE_PATH, "Continuing the saved game");
+        }
+        Err(error) => error!(path = SAVE_FILE_PATH, %error, "Could not load the saved game"),
+    }
+}
+
+// A finished run can't be continued
+pub fn delete_saved_game() {
+    if has_saved_game()
+        && let Err(error) = std::fs::remove_file(SAVE_FILE_PATH)
+    {
+        error!(path = SAVE_FILE_PATH, %error, "Could not delete the saved game");
+    }
+}
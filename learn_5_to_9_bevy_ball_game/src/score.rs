@@ -5,33 +5,159 @@ pub struct ScorePlugin;
 
 impl Plugin for ScorePlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<Score>()
+        app.register_type::<Score>()
+            .register_type::<HighScore>()
+            .register_type::<Combo>()
+            .init_resource::<Score>()
             .init_resource::<HighScore>()
+            .init_resource::<Combo>()
+            .add_event::<ScoreEvent>()
+            .add_systems(FixedUpdate, apply_score_events)
             .add_systems(
                 Update,
-                (update_score, update_high_scores, high_scores_updated),
+                (
+                    spawn_score_popups,
+                    update_score_popups,
+                    update_score,
+                    update_high_scores,
+                    high_scores_updated,
+                ),
+            )
+            .add_systems(
+                FixedUpdate,
+                tick_combo
+                    .run_if(in_state(AppState::Game))
+                    .run_if(in_state(SimulationState::Running)),
             );
     }
 }
 
-#[derive(Resource, Default)]
+#[derive(Resource, Reflect, Debug, Default)]
+#[reflect(Resource)]
 pub struct Score {
     pub value: u32,
 }
 
-#[derive(Resource, Debug)]
+#[derive(Resource, Reflect, Debug, Default)]
+#[reflect(Resource)]
 pub struct HighScore {
     pub scores: Vec<(String, u32)>,
 }
 
-impl Default for HighScore {
+#[derive(Resource, Reflect, Debug)]
+#[reflect(Resource)]
+pub struct Combo {
+    pub multiplier: u32,
+    pub window: Timer,
+    pub decay: Timer,
+}
+
+impl Default for Combo {
     fn default() -> Self {
-        Self { scores: Vec::new() }
+        let mut window = Timer::from_seconds(COMBO_WINDOW, TimerMode::Once);
+        window.tick(window.duration());
+
+        Self {
+            multiplier: 1,
+            window,
+            decay: Timer::from_seconds(COMBO_DECAY_TIME, TimerMode::Repeating),
+        }
+    }
+}
+
+impl Combo {
+    // Collecting a star while the previous pickup's window is still open grows the chain
+    pub fn register_star(&mut self) -> u32 {
+        if !self.window.finished() {
+            self.multiplier = (self.multiplier + 1).min(MAX_COMBO_MULTIPLIER);
+        }
+        self.window.reset();
+        self.decay.reset();
+
+        STAR_POINTS * self.multiplier
+    }
+
+    pub fn near_miss_points(&self) -> u32 {
+        NEAR_MISS_POINTS * self.multiplier
     }
 }
+
+#[derive(Component)]
+pub struct ScorePopup {
+    pub timer: Timer,
+}
+
+pub fn tick_combo(mut combo: ResMut<Combo>, time: Res<Time>) {
+    combo.window.tick(time.delta());
+
+    if combo.window.finished() && combo.multiplier > 1 {
+        combo.decay.tick(time.delta());
+
+        let steps = combo.decay.times_finished_this_tick();
+        combo.multiplier = combo.multiplier.saturating_sub(steps).max(1);
+    }
+}
+
+pub fn apply_score_events(
+    mut score_event_reader: EventReader<ScoreEvent>,
+    mut score: ResMut<Score>,
+) {
+    for event in score_event_reader.read() {
+        score.value += event.points;
+    }
+}
+
+pub fn spawn_score_popups(
+    mut commands: Commands,
+    mut score_event_reader: EventReader<ScoreEvent>,
+    preferences: Res<Preferences>,
+) {
+    if !preferences.score_popups {
+        score_event_reader.clear();
+        return;
+    }
+
+    for event in score_event_reader.read() {
+        let text = match (event.kind, event.multiplier) {
+            (ScoreKind::Star, 1) => format!("+{}", event.points),
+            (ScoreKind::Star, multiplier) => format!("+{} x{}", event.points, multiplier),
+            (ScoreKind::NearMiss, _) => format!("+{} close call!", event.points),
+        };
+
+        commands.spawn((
+            Text2d::new(text),
+            TextFont {
+                font_size: SCORE_POPUP_FONT_SIZE,
+                ..default()
+            },
+            Transform::from_xyz(event.position.x, event.position.y, 1.0),
+            ScorePopup {
+                timer: Timer::from_seconds(SCORE_POPUP_TIME, TimerMode::Once),
+            },
+        ));
+    }
+}
+
+pub fn update_score_popups(
+    mut commands: Commands,
+    mut popup_query: Query<(Entity, &mut ScorePopup, &mut Transform, &mut TextColor)>,
+    time: Res<Time>,
+) {
+    for (popup_entity, mut popup, mut transform, mut text_color) in popup_query.iter_mut() {
+        popup.timer.tick(time.delta());
+
+        transform.translation.y += SCORE_POPUP_SPEED * time.delta_secs();
+        text_color.0.set_alpha(popup.timer.fraction_remaining());
+
+        if popup.timer.finished() {
+            commands.entity(popup_entity).despawn();
+        }
+    }
+}
+
 pub fn update_score(score: Res<Score>) {
     if score.is_changed() {
-        println!("Score: {}", score.value);
+        debug!(score = score.value, "Score changed");
     }
 }
 
@@ -45,6 +171,6 @@ pub fn update_high_scores(
 }
 pub fn high_scores_updated(high_scores: Res<HighScore>) {
     if high_scores.is_changed() {
-        println!("High Scores: {:?}", high_scores)
+        info!(high_scores = ?high_scores.scores, "High scores updated");
     }
 }
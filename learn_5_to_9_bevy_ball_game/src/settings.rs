@@ -1,21 +1,139 @@
+use bevy::log::Level;
+use bevy::prelude::*;
 use std::ops::Range;
 
+// Game settings
+pub const RNG_SEED: Option<u64> = None;
+pub const ARENA_WIDTH: f32 = 1280.0;
+pub const ARENA_HEIGHT: f32 = 720.0;
+pub const LARGE_ARENA_WIDTH: f32 = 3840.0;
+pub const LARGE_ARENA_HEIGHT: f32 = 2160.0;
+// The most of the arena the camera shows at once. Larger arenas scroll.
+pub const CAMERA_VIEW_WIDTH: f32 = 1280.0;
+pub const CAMERA_VIEW_HEIGHT: f32 = 720.0;
+pub const LETTERBOX_COLOR: Color = Color::BLACK;
+pub const LETTERBOX_RENDER_LAYER: usize = 1;
+pub const SAVE_FILE_PATH: &str = "savegame.ron";
+pub const SCENE_EXPORT_PATH: &str = "scenes/world.scn.ron";
+pub const EXPORT_SCENE_KEY: KeyCode = KeyCode::F5;
+pub const LOAD_SCENE_KEY: KeyCode = KeyCode::F9;
+pub const DEBUG_OVERLAY_KEY: KeyCode = KeyCode::F3;
+pub const SETTINGS_KEY: KeyCode = KeyCode::KeyO;
+pub const PREFERENCES_FILE_PATH: &str = "preferences.ron";
+pub const WINDOW_RESOLUTIONS: [[u32; 2]; 4] =
+    [[1280, 720], [1600, 900], [1920, 1080], [2560, 1440]];
+
+// Log settings
+pub const LOG_LEVEL: Level = Level::INFO;
+pub const LOG_FILTER: &str = "wgpu=error,naga=warn,learn_2_bevy_ball_game=info";
+pub const LOG_FILE_PATH: Option<&str> = None;
+
 // Player settings
 pub const PLAYER_SPEED: f32 = 250.0;
+pub const SPRINT_MULTIPLIER: f32 = 2.0;
 pub const PLAYER_SCALE: f32 = 30.0;
-pub const LOG_PLAYER_MOVEMENT: bool = false;
-pub const LOG_COLLECTING_STARS: bool = true;
+pub const PLAYER_LIVES: u32 = 3;
+pub const PLAYER_INVULNERABILITY_TIME: f32 = 2.0;
+pub const PLAYER_BLINK_INTERVAL: f32 = 0.1;
+pub const PLAYER_RESPAWN_CANDIDATES: usize = 16;
+
+// Ability settings
+pub const DASH_KEY: KeyCode = KeyCode::KeyE;
+pub const DASH_SPEED: f32 = 900.0;
+pub const DASH_DURATION: f32 = 0.15;
+pub const DASH_COOLDOWN: f32 = 2.0;
+pub const DASH_COLOR: Color = Color::srgb(0.6, 1.0, 0.6);
+pub const SHIELD_KEY: KeyCode = KeyCode::KeyQ;
+pub const SHIELD_RADIUS: f32 = 30.0;
+pub const SHIELD_DURATION: f32 = 1.5;
+pub const SHIELD_COOLDOWN: f32 = 6.0;
+pub const SHIELD_COLOR: Color = Color::srgb(0.5, 0.8, 1.0);
+
+// Bot settings
+pub const BOT_LOOKAHEAD_TIME: f32 = 0.8;
+pub const BOT_LOOKAHEAD_STEPS: u32 = 8;
+pub const BOT_SAFETY_MARGIN: f32 = 25.0;
+pub const BOT_DANGER_WEIGHT: f32 = 40.0;
+pub const BOT_SHIELD_TIME: f32 = 0.2;
 
 // Star settings
 pub const STAR_SCALE: f32 = 30.0;
 pub const NUMBER_OF_STARS_AT_STARTUP: usize = 10;
 pub const STAR_SPAWN_TIME: f32 = 3.0;
 pub const AMOUNT_OF_STARS_PER_SPAWN: usize = 2;
+pub const MAX_NUMBER_OF_STARS: usize = 20;
+pub const STAR_LIFETIME: f32 = 15.0;
+pub const STAR_BLINK_TIME: f32 = 3.0;
+pub const STAR_BLINK_INTERVAL: f32 = 0.2;
+pub const STAR_SPAWN_CANDIDATES: usize = 4;
 
 // Enemy settings
 pub const ENEMY_SCALE: f32 = 25.0;
-pub const PLAY_ENEMY_BOUNCE_SOUND: bool = false;
 pub const NUMBER_OF_ENEMIES: usize = 7;
-pub const ENEMY_SPEED_VARIATY: Range<f32> = (25.0)..(200.0);
+pub const ENEMY_SPEED_VARIATY: Range<f32> = 25.0..200.0;
 pub const ENEMY_SPAWN_TIMER: f32 = 2.0;
 pub const ENEMY_SPAWN_OVER_TIME: usize = 3;
+
+// Score settings
+pub const STAR_POINTS: u32 = 1;
+pub const NEAR_MISS_POINTS: u32 = 1;
+pub const NEAR_MISS_DISTANCE: f32 = 20.0;
+pub const COMBO_WINDOW: f32 = 2.0;
+pub const COMBO_DECAY_TIME: f32 = 1.5;
+pub const MAX_COMBO_MULTIPLIER: u32 = 5;
+pub const SCORE_POPUP_TIME: f32 = 0.8;
+pub const SCORE_POPUP_SPEED: f32 = 40.0;
+pub const SCORE_POPUP_FONT_SIZE: f32 = 18.0;
+
+// HUD settings
+pub const HUD_MARGIN: f32 = 10.0;
+pub const HUD_ICON_SIZE: f32 = 48.0;
+pub const HUD_FONT_SIZE: f32 = 16.0;
+pub const HUD_COOLDOWN_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.6);
+
+// Audio settings
+pub const MASTER_VOLUME: f32 = 1.0;
+pub const SFX_VOLUME: f32 = 1.0;
+pub const MUSIC_VOLUME: f32 = 0.6;
+pub const UI_VOLUME: f32 = 0.8;
+pub const SOUND_TABLE_PATH: &str = "audio/sounds.ron";
+pub const SOUND_MIN_INTERVAL: f32 = 0.05;
+pub const SOUND_MAX_INSTANCES: usize = 3;
+pub const VOLUME_STEP: f32 = 0.1;
+pub const MAIN_MENU_MUSIC: &str = "audio/computerNoise_000.ogg";
+pub const GAME_MUSIC: &str = "audio/spaceEngineLow_000.ogg";
+pub const GAME_OVER_MUSIC: &str = "audio/computerNoise_003.ogg";
+pub const MUSIC_CROSSFADE_TIME: f32 = 1.5;
+pub const MUSIC_DUCK_TIME: f32 = 0.3;
+pub const MUSIC_PAUSED_LEVEL: f32 = 0.3;
+
+// Particle settings
+pub const PARTICLE_BUDGET: usize = 400;
+pub const PARTICLE_DRAG: f32 = 3.0;
+
+// Menu settings
+pub const MENU_SPACING: f32 = 12.0;
+pub const MENU_FONT_SIZE: f32 = 24.0;
+pub const MENU_TITLE_FONT_SIZE: f32 = 48.0;
+pub const MENU_BACKGROUND_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.7);
+pub const MENU_SELECTED_COLOR: Color = Color::srgb(1.0, 0.85, 0.3);
+
+// Minimap settings
+pub const MINIMAP_WIDTH: f32 = 240.0;
+pub const MINIMAP_DOT_SIZE: f32 = 4.0;
+pub const MINIMAP_BACKGROUND_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.6);
+pub const MINIMAP_VIEW_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.5);
+pub const MINIMAP_PLAYER_COLOR: Color = Color::srgb(0.3, 0.6, 1.0);
+pub const MINIMAP_ENEMY_COLOR: Color = Color::srgb(1.0, 0.3, 0.3);
+pub const MINIMAP_STAR_COLOR: Color = Color::srgb(1.0, 0.9, 0.3);
+
+// Debug overlay settings
+pub const DEBUG_FONT_SIZE: f32 = 14.0;
+pub const DEBUG_PANEL_WIDTH: f32 = 320.0;
+pub const DEBUG_BACKGROUND_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.6);
+pub const DEBUG_PLAYER_COLOR: Color = Color::srgb(0.2, 1.0, 0.2);
+pub const DEBUG_ENEMY_COLOR: Color = Color::srgb(1.0, 0.2, 0.2);
+pub const DEBUG_STAR_COLOR: Color = Color::srgb(1.0, 1.0, 0.2);
+pub const DEBUG_INSPECTED_COLOR: Color = Color::WHITE;
+pub const DEBUG_DIRECTION_LENGTH: f32 = 40.0;
+pub const DEBUG_INSPECT_DISTANCE: f32 = 30.0;
@@ -0,0 +1,312 @@
+use crate::imports::*;
+use bevy::input::common_conditions::input_just_pressed;
+use bevy::prelude::*;
+
+pub struct SettingsMenuPlugin;
+
+impl Plugin for SettingsMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SettingsSelection>()
+            .add_systems(
+                Update,
+                open_settings_menu
+                    .run_if(input_just_pressed(SETTINGS_KEY))
+                    .run_if(in_state(SettingsState::Closed))
+                    .run_if(can_open_settings),
+            )
+            .add_systems(
+                Update,
+                (
+                    close_settings_menu.run_if(input_just_pressed(SETTINGS_KEY)),
+                    navigate_settings_menu,
+                    update_settings_menu,
+                )
+                    .chain()
+                    .run_if(in_state(SettingsState::Open)),
+            )
+            .add_systems(OnEnter(SettingsState::Open), spawn_settings_menu)
+            .add_systems(
+                OnExit(SettingsState::Open),
+                (despawn_settings_menu, save_preferences),
+            );
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsRow {
+    MasterVolume,
+    SfxVolume,
+    MusicVolume,
+    UiVolume,
+    WindowMode,
+    Resolution,
+    Vsync,
+    ControlScheme,
+    EnemyBounceSound,
+    ScorePopups,
+}
+
+pub const SETTINGS_ROWS: [SettingsRow; 10] = [
+    SettingsRow::MasterVolume,
+    SettingsRow::SfxVolume,
+    SettingsRow::MusicVolume,
+    SettingsRow::UiVolume,
+    SettingsRow::WindowMode,
+    SettingsRow::Resolution,
+    SettingsRow::Vsync,
+    SettingsRow::ControlScheme,
+    SettingsRow::EnemyBounceSound,
+    SettingsRow::ScorePopups,
+];
+
+impl SettingsRow {
+    pub fn label(self) -> &'static str {
+        match self {
+            SettingsRow::MasterVolume => "Master volume",
+            SettingsRow::SfxVolume => "Effects volume",
+            SettingsRow::MusicVolume => "Music volume",
+            SettingsRow::UiVolume => "Interface volume",
+            SettingsRow::WindowMode => "Window mode",
+            SettingsRow::Resolution => "Resolution",
+            SettingsRow::Vsync => "Vsync",
+            SettingsRow::ControlScheme => "Controls",
+            SettingsRow::EnemyBounceSound => "Enemy bounce sound",
+            SettingsRow::ScorePopups => "Score popups",
+        }
+    }
+
+    pub fn value(self, preferences: &Preferences) -> String {
+        let percent = |volume: f32| format!("{:.0}%", volume * 100.0);
+        let on_off = |enabled: bool| if enabled { "On" } else { "Off" }.to_string();
+
+        match self {
+            SettingsRow::MasterVolume => percent(preferences.master_volume),
+            SettingsRow::SfxVolume => percent(preferences.sfx_volume),
+            SettingsRow::MusicVolume => percent(preferences.music_volume),
+            SettingsRow::UiVolume => percent(preferences.ui_volume),
+            SettingsRow::WindowMode => match preferences.window_mode {
+                PreferredWindowMode::Windowed => "Windowed",
+                PreferredWindowMode::BorderlessFullscreen => "Borderless fullscreen",
+                PreferredWindowMode::Fullscreen => "Fullscreen",
+            }
+            .to_string(),
+            SettingsRow::Resolution => {
+                format!(
+                    "{}x{}",
+                    preferences.resolution[0], preferences.resolution[1]
+                )
+            }
+            SettingsRow::Vsync => on_off(preferences.vsync),
+            SettingsRow::ControlScheme => match preferences.control_scheme {
+                ControlScheme::WasdAndArrows => "WASD and arrow keys",
+                ControlScheme::Wasd => "WASD",
+                ControlScheme::Arrows => "Arrow keys",
+            }
+            .to_string(),
+            SettingsRow::EnemyBounceSound => on_off(preferences.enemy_bounce_sound),
+            SettingsRow::ScorePopups => on_off(preferences.score_popups),
+        }
+    }
+
+    // Moves the setting `step` options along. Volumes stop at their ends, everything
+    // else wraps around.
+    pub fn adjust(self, preferences: &mut Preferences, step: i32) {
+        match self {
+            SettingsRow::MasterVolume => adjust_volume(&mut preferences.master_volume, step),
+            SettingsRow::SfxVolume => adjust_volume(&mut preferences.sfx_volume, step),
+            SettingsRow::MusicVolume => adjust_volume(&mut preferences.music_volume, step),
+            SettingsRow::UiVolume => adjust_volume(&mut preferences.ui_volume, step),
+            SettingsRow::WindowMode => {
+                preferences.window_mode = cycle(
+                    &[
+                        PreferredWindowMode::Windowed,
+                        PreferredWindowMode::BorderlessFullscreen,
+                        PreferredWindowMode::Fullscreen,
+                    ],
+                    preferences.window_mode,
+                    step,
+                )
+            }
+            SettingsRow::Resolution => {
+                preferences.resolution = cycle(&WINDOW_RESOLUTIONS, preferences.resolution, step)
+            }
+            SettingsRow::Vsync => preferences.vsync = !preferences.vsync,
+            SettingsRow::ControlScheme => {
+                preferences.control_scheme = cycle(
+                    &[
+                        ControlScheme::WasdAndArrows,
+                        ControlScheme::Wasd,
+                        ControlScheme::Arrows,
+                    ],
+                    preferences.control_scheme,
+                    step,
+                )
+            }
+            SettingsRow::EnemyBounceSound => {
+                preferences.enemy_bounce_sound = !preferences.enemy_bounce_sound
+            }
+            SettingsRow::ScorePopups => preferences.score_popups = !preferences.score_popups,
+        }
+    }
+}
+
+// Dividing keeps volumes on round values, like 0.9, instead of drifting off them
+fn adjust_volume(volume: &mut f32, step: i32) {
+    let steps_per_volume = (1.0 / VOLUME_STEP).round();
+    let steps = (*volume * steps_per_volume).round() + step as f32;
+    *volume = (steps / steps_per_volume).clamp(0.0, 1.0);
+}
+
+// An option that isn't in the list, like a resolution edited into the file by hand,
+// starts over from the first one
+fn cycle<T: Copy + PartialEq>(options: &[T], current: T, step: i32) -> T {
+    let index = options
+        .iter()
+        .position(|option| *option == current)
+        .map_or(0, |index| {
+            (index as i32 + step).rem_euclid(options.len() as i32) as usize
+        });
+    options[index]
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct SettingsSelection {
+    pub row: usize,
+}
+
+#[derive(Component)]
+pub struct SettingsMenuScreen;
+
+#[derive(Component)]
+pub struct SettingsRowText(pub usize);
+
+// Settings can be changed from the main menu, or while the game is paused
+pub fn can_open_settings(
+    app_state: Res<State<AppState>>,
+    simulation_state: Res<State<SimulationState>>,
+) -> bool {
+    match app_state.get() {
+        AppState::MainMenu => true,
+        AppState::Game => *simulation_state.get() == SimulationState::Paused,
+        AppState::GameOver => false,
+    }
+}
+
+pub fn open_settings_menu(mut commands: Commands, mut sound_event_writer: EventWriter<SoundEvent>) {
+    commands.insert_resource(NextState::Pending(SettingsState::Open));
+    sound_event_writer.write(SoundEvent::UiClick);
+}
+
+pub fn close_settings_menu(
+    mut commands: Commands,
+    mut sound_event_writer: EventWriter<SoundEvent>,
+) {
+    commands.insert_resource(NextState::Pending(SettingsState::Closed));
+    sound_event_writer.write(SoundEvent::UiClick);
+}
+
+pub fn navigate_settings_menu(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut selection: ResMut<SettingsSelection>,
+    mut preferences: ResMut<Preferences>,
+    mut sound_event_writer: EventWriter<SoundEvent>,
+) {
+    let rows = SETTINGS_ROWS.len();
+
+    if keyboard_input.any_just_pressed([KeyCode::ArrowUp, KeyCode::KeyW]) {
+        selection.row = (selection.row + rows - 1) % rows;
+        sound_event_writer.write(SoundEvent::UiClick);
+    }
+    if keyboard_input.any_just_pressed([KeyCode::ArrowDown, KeyCode::KeyS]) {
+        selection.row = (selection.row + 1) % rows;
+        sound_event_writer.write(SoundEvent::UiClick);
+    }
+
+    let step = if keyboard_input.any_just_pressed([KeyCode::ArrowLeft, KeyCode::KeyA]) {
+        -1
+    } else if keyboard_input.any_just_pressed([KeyCode::ArrowRight, KeyCode::KeyD, KeyCode::Enter])
+    {
+        1
+    } else {
+        return;
+    };
+
+    SETTINGS_ROWS[selection.row].adjust(&mut preferences, step);
+    sound_event_writer.write(SoundEvent::UiClick);
+}
+
+pub fn spawn_settings_menu(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(MENU_SPACING),
+                ..default()
+            },
+            BackgroundColor(MENU_BACKGROUND_COLOR),
+            // Covers whichever screen it was opened from
+            GlobalZIndex(1),
+            SettingsMenuScreen,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("Settings"),
+                TextFont {
+                    font_size: MENU_TITLE_FONT_SIZE,
+                    ..default()
+                },
+            ));
+            for row in 0..SETTINGS_ROWS.len() {
+                parent.spawn((
+                    Text::default(),
+                    TextFont {
+                        font_size: MENU_FONT_SIZE,
+                        ..default()
+                    },
+                    SettingsRowText(row),
+                ));
+            }
+            parent.spawn((
+                Text::new("Up/Down to choose, Left/Right to change, O to go back"),
+                TextFont {
+                    font_size: MENU_FONT_SIZE,
+                    ..default()
+                },
+            ));
+        });
+}
+
+pub fn update_settings_menu(
+    selection: Res<SettingsSelection>,
+    preferences: Res<Preferences>,
+    mut row_query: Query<(&SettingsRowText, &mut Text, &mut TextColor)>,
+) {
+    for (row_text, mut text, mut text_color) in &mut row_query {
+        let row = SETTINGS_ROWS[row_text.0];
+        let selected = row_text.0 == selection.row;
+
+        text.0 = if selected {
+            format!("< {}: {} >", row.label(), row.value(&preferences))
+        } else {
+            format!("{}: {}", row.label(), row.value(&preferences))
+        };
+        text_color.0 = if selected {
+            MENU_SELECTED_COLOR
+        } else {
+            Color::WHITE
+        };
+    }
+}
+
+pub fn despawn_settings_menu(
+    mut commands: Commands,
+    screen_query: Query<Entity, With<SettingsMenuScreen>>,
+) {
+    for screen_entity in screen_query {
+        commands.entity(screen_entity).despawn();
+    }
+}
@@ -1,94 +1,209 @@
 use crate::imports::*;
-use bevy::{prelude::*, window::PrimaryWindow};
-use rand::random;
+use bevy::prelude::*;
+use rand::Rng;
 
-#[derive(Component)]
-pub struct Star {}
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component, Default)]
+#[require(TranslationInterpolation)]
+pub struct Star {
+    pub lifetime: Timer,
+}
+
+impl Default for Star {
+    fn default() -> Self {
+        Self {
+            lifetime: Timer::from_seconds(STAR_LIFETIME, TimerMode::Once),
+        }
+    }
+}
 
 pub struct StarPlugin;
 
 impl Plugin for StarPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<StarSpawnTimer>()
-            .add_systems(Startup, spawn_stars)
+        app.register_type::<Star>()
+            .register_type::<StarSpawnTimer>()
+            .init_resource::<StarSpawnTimer>()
+            // Enemies draw from the seeded rng first so every run of a seed spawns the same
+            // world, and the player has to exist for the first stars to keep away from it
+            .add_systems(
+                Startup,
+                spawn_stars.after(spawn_enemies).after(spawn_players),
+            )
             .add_systems(
-                Update,
-                (tick_star_spawn_timer, spawn_stars_over_time)
+                FixedUpdate,
+                (
+                    tick_star_spawn_timer,
+                    spawn_stars_over_time,
+                    tick_star_lifetime,
+                    blink_expiring_stars,
+                    despawn_expired_stars,
+                )
                     .run_if(in_state(AppState::Game))
                     .run_if(in_state(SimulationState::Running)),
             );
     }
 }
 
-#[derive(Resource)]
+#[derive(Resource, Reflect, Debug)]
+#[reflect(Resource)]
 pub struct StarSpawnTimer {
     pub timer: Timer,
 }
 
-impl Default for StarSpawnTimer {
-    fn default() -> Self {
+impl FromWorld for StarSpawnTimer {
+    fn from_world(world: &mut World) -> Self {
+        let balance = world.resource::<Balance>();
+
         Self {
-            timer: Timer::from_seconds(STAR_SPAWN_TIME, TimerMode::Repeating),
+            timer: Timer::from_seconds(balance.star_spawn_time, TimerMode::Repeating),
         }
     }
 }
 
 pub fn spawn_stars(
     mut commands: Commands,
-    windows_query: Query<&Window, With<PrimaryWindow>>,
+    arena: Res<Arena>,
+    player_query: Query<&Transform, With<Player>>,
     assest_server: Res<AssetServer>,
+    mut game_rng: ResMut<GameRng>,
 ) {
-    let window = windows_query.single().unwrap();
-
-    (0..NUMBER_OF_STARS_AT_STARTUP).into_iter().for_each(|_| {
-        let random_x = random::<f32>() * window.width();
-        let random_y = random::<f32>() * window.height();
-
-        commands.spawn((
-            Sprite {
-                image: assest_server.load("sprites/star.png"),
-                custom_size: Some(Vec2 {
-                    x: STAR_SCALE,
-                    y: STAR_SCALE,
-                }),
-                ..default()
-            },
-            Transform::from_xyz(random_x, random_y, 0.0),
-            Star {},
-        ));
+    let player_position = player_query
+        .single()
+        .ok()
+        .map(|transform| transform.translation.truncate());
+
+    (0..NUMBER_OF_STARS_AT_STARTUP).for_each(|_| {
+        let position = random_star_position(&arena, player_position, &mut game_rng);
+
+        spawn_star(&mut commands, &assest_server, position);
     });
 }
+
 pub fn tick_star_spawn_timer(mut star_spawn_timer: ResMut<StarSpawnTimer>, time: Res<Time>) {
     star_spawn_timer.timer.tick(time.delta());
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn spawn_stars_over_time(
     mut commands: Commands,
-    windows_query: Query<&Window, With<PrimaryWindow>>,
+    arena: Res<Arena>,
+    player_query: Query<&Transform, With<Player>>,
+    star_query: Query<(), With<Star>>,
     star_spawn_timer: Res<StarSpawnTimer>,
     assest_server: Res<AssetServer>,
+    mut sound_event_writer: EventWriter<SoundEvent>,
+    mut game_rng: ResMut<GameRng>,
 ) {
     if star_spawn_timer.timer.finished() {
-        let window = windows_query.single().unwrap();
-
-        commands.spawn(AudioPlayer::new(assest_server.load("audio/select_001.ogg")));
-
-        (0..AMOUNT_OF_STARS_PER_SPAWN).for_each(|_| {
-            let random_x = random::<f32>() * window.width();
-            let random_y = random::<f32>() * window.height();
-
-            commands.spawn((
-                Sprite {
-                    image: assest_server.load("sprites/star.png"),
-                    custom_size: Some(Vec2 {
-                        x: STAR_SCALE,
-                        y: STAR_SCALE,
-                    }),
-                    ..default()
-                },
-                Transform::from_xyz(random_x, random_y, 0.0),
-                Star {},
-            ));
+        let player_position = player_query
+            .single()
+            .ok()
+            .map(|transform| transform.translation.truncate());
+
+        let free_slots = MAX_NUMBER_OF_STARS.saturating_sub(star_query.iter().count());
+        let amount_to_spawn = AMOUNT_OF_STARS_PER_SPAWN.min(free_slots);
+
+        if amount_to_spawn == 0 {
+            return;
+        }
+
+        sound_event_writer.write(SoundEvent::StarsSpawned);
+
+        (0..amount_to_spawn).for_each(|_| {
+            let position = random_star_position(&arena, player_position, &mut game_rng);
+
+            spawn_star(&mut commands, &assest_server, position);
         })
     }
 }
+
+pub fn tick_star_lifetime(mut star_query: Query<&mut Star>, time: Res<Time>) {
+    for mut star in star_query.iter_mut() {
+        star.lifetime.tick(time.delta());
+    }
+}
+
+pub fn blink_expiring_stars(mut star_query: Query<(&Star, &mut Visibility)>) {
+    for (star, mut visibility) in star_query.iter_mut() {
+        let remaining = star.lifetime.remaining_secs();
+
+        let new_visibility = if remaining > STAR_BLINK_TIME {
+            Visibility::Inherited
+        } else if ((remaining / STAR_BLINK_INTERVAL) as u32).is_multiple_of(2) {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        };
+
+        visibility.set_if_neq(new_visibility);
+    }
+}
+
+pub fn despawn_expired_stars(mut commands: Commands, star_query: Query<(Entity, &Star)>) {
+    for (star_entity, star) in star_query {
+        if star.lifetime.finished() {
+            commands.entity(star_entity).despawn();
+        }
+    }
+}
+
+pub fn spawn_star(commands: &mut Commands, assest_server: &AssetServer, position: Vec2) -> Entity {
+    commands
+        .spawn((
+            star_sprite(assest_server),
+            Transform::from_xyz(position.x, position.y, 0.0),
+            Star::default(),
+        ))
+        .id()
+}
+
+pub fn star_sprite(assest_server: &AssetServer) -> Sprite {
+    Sprite {
+        image: assest_server.load("sprites/star.png"),
+        custom_size: Some(Vec2 {
+            x: STAR_SCALE,
+            y: STAR_SCALE,
+        }),
+        ..default()
+    }
+}
+
+// Candidates further away from the player are proportionally more likely to be picked
+fn random_star_position(
+    arena: &Arena,
+    player_position: Option<Vec2>,
+    game_rng: &mut GameRng,
+) -> Vec2 {
+    let mut random_position = || {
+        Vec2::new(
+            game_rng.random::<f32>() * arena.width,
+            game_rng.random::<f32>() * arena.height,
+        )
+    };
+
+    let Some(player_position) = player_position else {
+        return random_position();
+    };
+
+    let candidates: Vec<Vec2> = (0..STAR_SPAWN_CANDIDATES.max(1))
+        .map(|_| random_position())
+        .collect();
+
+    let total_distance: f32 = candidates
+        .iter()
+        .map(|candidate| candidate.distance(player_position))
+        .sum();
+
+    let mut pick = game_rng.random::<f32>() * total_distance;
+
+    for candidate in &candidates {
+        let distance = candidate.distance(player_position);
+        if pick < distance {
+            return *candidate;
+        }
+        pick -= distance;
+    }
+
+    *candidates.last().unwrap()
+}
@@ -0,0 +1,180 @@
+use crate::imports::*;
+use bevy::ecs::entity::EntityHashMap;
+use bevy::input::common_conditions::input_just_pressed;
+use bevy::prelude::*;
+use bevy::scene::ron;
+use bevy::scene::serde::SceneDeserializer;
+use serde::de::DeserializeSeed;
+use std::io;
+use std::path::Path;
+
+pub struct WorldScenePlugin;
+
+impl Plugin for WorldScenePlugin {
+    fn build(&self, app: &mut App) {
+        // Headless apps don't add the plugin that registers it
+        app.register_type::<Transform>().add_systems(
+            Update,
+            (
+                export_world_scene_file.run_if(input_just_pressed(EXPORT_SCENE_KEY)),
+                load_world_scene_file.run_if(input_just_pressed(LOAD_SCENE_KEY)),
+            )
+                .run_if(in_state(AppState::Game)),
+        );
+
+        // A hand-authored layout replaces whatever startup spawned
+        if let Some(path) = cli_arg("--layout") {
+            app.insert_resource(StartingLayout(path)).add_systems(
+                Startup,
+                load_starting_layout
+                    .after(spawn_players)
+                    .after(spawn_enemies)
+                    .after(spawn_stars),
+            );
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct StartingLayout(pub String);
+
+// The gameplay state of the arena. Sprites and interpolation are left out, they are
+// rebuilt from the gameplay components when the scene is loaded.
+pub fn world_scene(world: &World) -> DynamicScene {
+    let entities = world
+        .iter_entities()
+        .filter(|entity| {
+            entity.contains::<Player>() || entity.contains::<Enemy>() || entity.contains::<Star>()
+        })
+        .map(|entity| entity.id());
+
+    DynamicSceneBuilder::from_world(world)
+        .deny_all()
+        .allow_component::<Transform>()
+        .allow_component::<Player>()
+        .allow_component::<Lives>()
+        .allow_component::<Invulnerable>()
+        .allow_component::<Dash>()
+        .allow_component::<Shield>()
+        .allow_component::<Enemy>()
+        .allow_component::<NearMiss>()
+        .allow_component::<Star>()
+        .allow_resource::<Score>()
+        .allow_resource::<HighScore>()
+        .allow_resource::<Combo>()
+        .allow_resource::<EnemySpawnTime>()
+        .allow_resource::<StarSpawnTimer>()
+        .extract_entities(entities)
+        .extract_resources()
+        .build()
+}
+
+// Exports where the simulation has the entities, rather than where they are drawn
+// between two fixed updates
+pub fn export_world_scene(world: &mut World) -> io::Result<String> {
+    for (mut transform, interpolation) in world
+        .query::<(&mut Transform, &TranslationInterpolation)>()
+        .iter_mut(world)
+    {
+        transform.translation = interpolation.current;
+    }
+
+    let type_registry = world.resource::<AppTypeRegistry>().read();
+
+    world_scene(world)
+        .serialize(&type_registry)
+        .map_err(io::Error::other)
+}
+
+// Replaces the player, enemies and stars with the ones in the scene
+pub fn load_world_scene(world: &mut World, scene_text: &str) -> io::Result<()> {
+    let invalid = |error: String| io::Error::new(io::ErrorKind::InvalidData, error);
+
+    let scene = {
+        let type_registry = world.resource::<AppTypeRegistry>().read();
+        let mut deserializer = ron::de::Deserializer::from_str(scene_text)
+            .map_err(|error| invalid(error.to_string()))?;
+
+        SceneDeserializer {
+            type_registry: &type_registry,
+        }
+        .deserialize(&mut deserializer)
+        .map_err(|error| invalid(error.to_string()))?
+    };
+
+    let entities: Vec<Entity> = world
+        .query_filtered::<Entity, Or<(With<Player>, With<Enemy>, With<Star>)>>()
+        .iter(world)
+        .collect();
+    for entity in entities {
+        world.despawn(entity);
+    }
+
+    let mut entity_map = EntityHashMap::default();
+    scene
+        .write_to_world(world, &mut entity_map)
+        .map_err(|error| invalid(error.to_string()))?;
+
+    let assest_server = world.resource::<AssetServer>().clone();
+    let loaded_entities: Vec<Entity> = entity_map.values().copied().collect();
+    for entity in loaded_entities {
+        let mut entity = world.entity_mut(entity);
+
+        let sprite = if entity.contains::<Player>() {
+            player_sprite(&assest_server)
+        } else if entity.contains::<Enemy>() {
+            enemy_sprite(&assest_server)
+        } else {
+            star_sprite(&assest_server)
+        };
+        entity.insert(sprite);
+
+        // Whatever order the scene inserted components in, start interpolating from
+        // the loaded position
+        let translation = entity
+            .get::<Transform>()
+            .map(|transform| transform.translation)
+            .unwrap_or_default();
+        if let Some(mut interpolation) = entity.get_mut::<TranslationInterpolation>() {
+            interpolation.previous = translation;
+            interpolation.current = translation;
+        }
+    }
+
+    Ok(())
+}
+
+pub fn export_world_scene_file(world: &mut World) {
+    let path = Path::new(SCENE_EXPORT_PATH);
+
+    let result = export_world_scene(world).and_then(|scene_text| {
+        if let Some(directory) = path.parent() {
+            std::fs::create_dir_all(directory)?;
+        }
+        std::fs::write(path, scene_text)
+    });
+
+    match result {
+        Ok(()) => info!(path = %path.display(), "Exported the world"),
+        Err(error) => error!(path = %path.display(), %error, "Could not export the world"),
+    }
+}
+
+pub fn load_world_scene_file(world: &mut World) {
+    load_world_scene_from(world, SCENE_EXPORT_PATH);
+}
+
+pub fn load_starting_layout(world: &mut World) {
+    let path = world.resource::<StartingLayout>().0.clone();
+    load_world_scene_from(world, &path);
+}
+
+fn load_world_scene_from(world: &mut World, path: &str) {
+    let result =
+        std::fs::read_to_string(path).and_then(|scene_text| load_world_scene(world, &scene_text));
+
+    match result {
+        Ok(()) => info!(path, "Loaded the world"),
+        Err(error) => error!(path, %error, "Could not load the world"),
+    }
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use bevy::prelude::*;
+use common::*;
+use learn_2_bevy_ball_game::*;
+
+fn sound_players(app: &mut App) -> Vec<(AudioBus, f32)> {
+    app.world_mut()
+        .query_filtered::<(&AudioBus, &PlaybackSettings), With<SoundInstance>>()
+        .iter(app.world())
+        .map(|(bus, settings)| (*bus, settings.volume.to_linear()))
+        .collect()
+}
+
+#[test]
+fn sound_table_covers_every_sound_event() {
+    let sound_table = SoundTable::load(asset_file_path(SOUND_TABLE_PATH)).unwrap();
+
+    for sound in SoundEvent::ALL {
+        let sound_clips = &sound_table.0[&sound];
+        assert!(!sound_clips.clips.is_empty(), "{:?} has no clips", sound);
+        for clip in &sound_clips.clips {
+            assert!(
+                asset_file_path(clip).exists(),
+                "{:?} plays missing clip {}",
+                sound,
+                clip
+            );
+        }
+    }
+}
+
+#[test]
+fn simultaneous_sounds_are_rate_limited() {
+    let mut app = test_app();
+    for _ in 0..50 {
+        app.world_mut().send_event(SoundEvent::EnemyBounce);
+    }
+    app.world_mut().send_event(SoundEvent::EnemiesSpawned);
+    tick(&mut app, 1);
+
+    assert_eq!(sound_players(&mut app).len(), 2);
+}
+
+#[test]
+fn sounds_play_a_variant_at_their_bus_volume() {
+    let mut app = test_app();
+    app.insert_resource(AudioVolumes {
+        master: 0.5,
+        sfx: 0.8,
+        music: 1.0,
+        ui: 0.2,
+    });
+    app.world_mut().send_event(SoundEvent::StarCollected);
+    app.world_mut().send_event(SoundEvent::UiClick);
+    tick(&mut app, 1);
+
+    let mut players = sound_players(&mut app);
+    players.sort_by(|a, b| a.1.total_cmp(&b.1));
+    assert_eq!(players, vec![(AudioBus::Ui, 0.1), (AudioBus::Sfx, 0.4)]);
+
+    let sound_table = app.world().resource::<SoundTable>().clone();
+    let asset_server = app.world().resource::<AssetServer>().clone();
+    let mut sound_query = app
+        .world_mut()
+        .query::<(&SoundInstance, &AudioPlayer, &PlaybackSettings)>();
+    for (instance, player, settings) in sound_query.iter(app.world()) {
+        let sound_clips = &sound_table.0[&instance.sound];
+        let clip = asset_server.get_path(&player.0).unwrap().to_string();
+        assert!(sound_clips.clips.contains(&clip));
+        assert!((settings.speed - 1.0).abs() <= sound_clips.pitch_jitter);
+    }
+}
@@ -0,0 +1,30 @@
+use bevy::prelude::*;
+use learn_2_bevy_ball_game::*;
+
+#[test]
+fn bot_heads_for_the_nearest_star() {
+    let input = bot_input(
+        Vec2::new(400.0, 300.0),
+        &[Vec2::new(1000.0, 300.0), Vec2::new(400.0, 100.0)],
+        &[],
+        &Arena::default(),
+    );
+
+    assert_eq!(input.movement, Vec2::new(0.0, -1.0));
+}
+
+#[test]
+fn bot_leaves_the_path_of_an_oncoming_enemy() {
+    let player = Vec2::new(400.0, 300.0);
+    let input = bot_input(
+        player,
+        &[Vec2::new(700.0, 300.0)],
+        &[BotEnemy {
+            position: Vec2::new(500.0, 300.0),
+            velocity: Vec2::new(-200.0, 0.0),
+        }],
+        &Arena::default(),
+    );
+
+    assert_ne!(input.movement.y, 0.0);
+}
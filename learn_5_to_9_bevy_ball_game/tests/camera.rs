@@ -0,0 +1,51 @@
+use bevy::prelude::*;
+use learn_2_bevy_ball_game::*;
+
+#[test]
+fn letterbox_keeps_the_arena_aspect_ratio() {
+    let arena = Arena {
+        width: 1280.0,
+        height: 720.0,
+    };
+
+    // Same aspect ratio fills the window
+    assert_eq!(
+        letterbox(UVec2::new(1920, 1080), &arena),
+        Some((UVec2::ZERO, UVec2::new(1920, 1080)))
+    );
+    // Too wide gets bars on the sides
+    assert_eq!(
+        letterbox(UVec2::new(2000, 720), &arena),
+        Some((UVec2::new(360, 0), UVec2::new(1280, 720)))
+    );
+    // Too tall gets bars above and below
+    assert_eq!(
+        letterbox(UVec2::new(1280, 1000), &arena),
+        Some((UVec2::new(0, 140), UVec2::new(1280, 720)))
+    );
+    assert_eq!(letterbox(UVec2::new(0, 0), &arena), None);
+}
+
+#[test]
+fn camera_follows_the_player_only_in_a_large_arena() {
+    let arena = Arena::default();
+    let center = arena.size() / 2.0;
+
+    assert!(!arena.scrolls());
+    assert_eq!(camera_position(Vec2::new(100.0, 600.0), &arena), center);
+
+    let arena = Arena::large();
+    let half_view = arena.view_size() / 2.0;
+
+    assert!(arena.scrolls());
+    assert_eq!(
+        camera_position(Vec2::new(2000.0, 1000.0), &arena),
+        Vec2::new(2000.0, 1000.0)
+    );
+    // Near the edges the view stops instead of showing past them
+    assert_eq!(camera_position(Vec2::ZERO, &arena), half_view);
+    assert_eq!(
+        camera_position(arena.size(), &arena),
+        arena.size() - half_view
+    );
+}
@@ -0,0 +1,175 @@
+// Each test binary only uses some of these helpers
+#![allow(dead_code)]
+
+use bevy::input::ButtonState;
+use bevy::input::keyboard::{Key, KeyboardInput, NativeKey};
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use learn_2_bevy_ball_game::*;
+
+pub const TEST_SEED: u64 = 0;
+
+// A headless game with an empty arena, ready to have entities placed in it
+pub fn test_app() -> App {
+    test_app_with_balance(Balance::default())
+}
+
+pub fn test_app_with_balance(balance: Balance) -> App {
+    let mut app = seeded_test_app(TEST_SEED, balance);
+    clear_arena(&mut app);
+    app
+}
+
+// A headless game as startup populated it, for tests that play out a whole run
+pub fn test_app_with_seed(seed: u64) -> App {
+    seeded_test_app(seed, Balance::default())
+}
+
+fn seeded_test_app(seed: u64, balance: Balance) -> App {
+    let mut app = headless_app_with_balance(balance);
+    app.insert_resource(GameRng::new(seed))
+        .init_resource::<GameOverEvents>()
+        .add_systems(Last, collect_game_over_events);
+
+    let timestep = app.world().resource::<Time<Fixed>>().timestep();
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(timestep));
+
+    // Enters the game and lets startup populate the arena
+    app.update();
+
+    app
+}
+
+pub fn clear_arena(app: &mut App) {
+    let world = app.world_mut();
+    let entities: Vec<Entity> = world
+        .query_filtered::<Entity, Or<(With<Player>, With<Enemy>, With<Star>)>>()
+        .iter(world)
+        .collect();
+
+    for entity in entities {
+        world.despawn(entity);
+    }
+}
+
+// Each update advances the simulation by exactly one fixed timestep
+pub fn tick(app: &mut App, ticks: u32) {
+    for _ in 0..ticks {
+        app.update();
+    }
+}
+
+pub fn place_player(app: &mut App, position: Vec2) -> Entity {
+    app.world_mut()
+        .spawn((
+            Sprite::default(),
+            Transform::from_translation(position.extend(0.0)),
+            Player {},
+        ))
+        .id()
+}
+
+pub fn place_enemy(app: &mut App, position: Vec2, direction: Vec2, speed: f32) -> Entity {
+    app.world_mut()
+        .spawn((
+            Sprite::default(),
+            Transform::from_translation(position.extend(0.0)),
+            Enemy { direction, speed },
+        ))
+        .id()
+}
+
+pub fn place_star(app: &mut App, position: Vec2) -> Entity {
+    app.world_mut()
+        .spawn((
+            Sprite::default(),
+            Transform::from_translation(position.extend(0.0)),
+            Star::default(),
+        ))
+        .id()
+}
+
+// Where the simulation last put the entity, rather than where it is drawn between ticks
+pub fn position(app: &App, entity: Entity) -> Vec2 {
+    app.world()
+        .get::<TranslationInterpolation>(entity)
+        .expect("entity is not simulated")
+        .current
+        .truncate()
+}
+
+pub fn teleport(app: &mut App, entity: Entity, position: Vec2) {
+    let world = app.world_mut();
+    let (mut transform, mut interpolation) = world
+        .query::<(&mut Transform, &mut TranslationInterpolation)>()
+        .get_mut(world, entity)
+        .unwrap();
+
+    snap_translation(&mut transform, &mut interpolation, position.extend(0.0));
+}
+
+pub fn press_key(app: &mut App, key: KeyCode) {
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(key);
+}
+
+// Presses and releases a key over one update, the way a window reports a quick tap, so
+// systems see it as just pressed
+pub fn tap_key(app: &mut App, key: KeyCode) {
+    for state in [ButtonState::Pressed, ButtonState::Released] {
+        app.world_mut().send_event(KeyboardInput {
+            key_code: key,
+            logical_key: Key::Unidentified(NativeKey::Unidentified),
+            state,
+            text: None,
+            repeat: false,
+            window: Entity::PLACEHOLDER,
+        });
+        app.update();
+    }
+}
+
+pub fn set_simulation_state(app: &mut App, state: SimulationState) {
+    app.insert_resource(NextState::Pending(state));
+    app.update();
+}
+
+pub fn count<T: Component>(app: &mut App) -> usize {
+    let world = app.world_mut();
+    world.query_filtered::<(), With<T>>().iter(world).count()
+}
+
+pub fn score(app: &App) -> u32 {
+    app.world().resource::<Score>().value
+}
+
+pub fn game_over_events(app: &App) -> &[GameOver] {
+    &app.world().resource::<GameOverEvents>().events
+}
+
+// Events only live for a couple of updates, so keep every one the tests might ask about
+#[derive(Resource, Default)]
+pub struct GameOverEvents {
+    pub events: Vec<GameOver>,
+}
+
+fn collect_game_over_events(
+    mut game_over_event_reader: EventReader<GameOver>,
+    mut game_over_events: ResMut<GameOverEvents>,
+) {
+    game_over_events
+        .events
+        .extend(game_over_event_reader.read().copied());
+}
+
+// Entities can come back in a different order, which doesn't change the game
+pub fn sorted(mut save_game: SaveGame) -> SaveGame {
+    save_game
+        .enemies
+        .sort_by(|a, b| a.position.partial_cmp(&b.position).unwrap());
+    save_game
+        .stars
+        .sort_by(|a, b| a.position.partial_cmp(&b.position).unwrap());
+    save_game
+}
@@ -0,0 +1,30 @@
+mod common;
+
+use bevy::prelude::*;
+use common::*;
+use learn_2_bevy_ball_game::*;
+
+#[test]
+fn inspector_lists_gameplay_components() {
+    let mut app = test_app();
+    let enemy = place_enemy(&mut app, Vec2::new(100.0, 100.0), Vec2::X, 42.0);
+    tick(&mut app, 1);
+
+    let components = inspect_entity(app.world(), enemy).unwrap();
+    let names: Vec<&str> = components.iter().map(|(name, _)| name.as_str()).collect();
+    assert!(names.contains(&"Enemy"));
+    assert!(names.contains(&"Transform"));
+    assert!(!names.contains(&"Sprite"));
+
+    let (_, enemy_value) = components.iter().find(|(name, _)| name == "Enemy").unwrap();
+    assert!(enemy_value.contains("speed: 42.0"));
+}
+
+#[test]
+fn inspector_reports_despawned_entities() {
+    let mut app = test_app();
+    let star = place_star(&mut app, Vec2::new(100.0, 100.0));
+    app.world_mut().despawn(star);
+
+    assert!(inspect_entity(app.world(), star).is_none());
+}
@@ -0,0 +1,166 @@
+mod common;
+
+use bevy::prelude::*;
+use common::*;
+use learn_2_bevy_ball_game::*;
+
+#[test]
+fn collecting_a_star_scores_a_point() {
+    let mut app = test_app();
+    place_player(&mut app, Vec2::new(400.0, 300.0));
+    let star = place_star(&mut app, Vec2::new(410.0, 300.0));
+
+    // Score events are applied on the tick after the pickup
+    tick(&mut app, 2);
+
+    assert_eq!(score(&app), 1);
+    assert!(app.world().get_entity(star).is_err());
+}
+
+#[test]
+fn distant_star_is_not_collected() {
+    let mut app = test_app();
+    place_player(&mut app, Vec2::new(400.0, 300.0));
+    let star = place_star(&mut app, Vec2::new(800.0, 300.0));
+
+    tick(&mut app, 10);
+
+    assert_eq!(score(&app), 0);
+    assert!(app.world().get_entity(star).is_ok());
+}
+
+#[test]
+fn enemy_contact_on_last_life_ends_the_game() {
+    let mut app = test_app();
+    let player = place_player(&mut app, Vec2::new(400.0, 300.0));
+    app.world_mut().get_mut::<Lives>(player).unwrap().remaining = 1;
+    place_enemy(&mut app, Vec2::new(410.0, 300.0), Vec2::X, 0.0);
+
+    tick(&mut app, 2);
+
+    assert_eq!(game_over_events(&app), [GameOver { score: 0 }]);
+    assert!(app.world().get_entity(player).is_err());
+    assert_eq!(
+        *app.world().resource::<State<AppState>>().get(),
+        AppState::GameOver
+    );
+}
+
+#[test]
+fn g_on_the_game_over_screen_returns_to_the_main_menu() {
+    let mut app = test_app();
+    app.add_plugins(StatesPlugin);
+    let player = place_player(&mut app, Vec2::new(400.0, 300.0));
+    app.world_mut().get_mut::<Lives>(player).unwrap().remaining = 1;
+    place_enemy(&mut app, Vec2::new(410.0, 300.0), Vec2::X, 0.0);
+    tick(&mut app, 2);
+
+    tap_key(&mut app, KeyCode::KeyG);
+    tick(&mut app, 1);
+
+    assert_eq!(
+        *app.world().resource::<State<AppState>>().get(),
+        AppState::MainMenu
+    );
+}
+
+#[test]
+fn enemy_contact_with_lives_left_respawns_the_player() {
+    let mut app = test_app();
+    let player = place_player(&mut app, Vec2::new(400.0, 300.0));
+    place_enemy(&mut app, Vec2::new(410.0, 300.0), Vec2::X, 0.0);
+
+    tick(&mut app, 1);
+
+    assert!(game_over_events(&app).is_empty());
+    assert_eq!(
+        app.world().get::<Lives>(player).unwrap().remaining,
+        PLAYER_LIVES - 1
+    );
+    assert!(app.world().get::<Invulnerable>(player).is_some());
+
+    // The respawn is drawn where it lands instead of sliding over from the hit
+    let interpolation = app.world().get::<TranslationInterpolation>(player).unwrap();
+    assert_eq!(interpolation.previous, interpolation.current);
+}
+
+#[test]
+fn player_is_confined_to_the_arena() {
+    let mut app = test_app();
+    let arena_size = {
+        let arena = app.world().resource::<Arena>();
+        Vec2::new(arena.width, arena.height)
+    };
+    let half_player_size = PLAYER_SCALE / 2.0;
+
+    let player = place_player(&mut app, Vec2::new(-100.0, -100.0));
+    tick(&mut app, 1);
+    assert_eq!(position(&app, player), Vec2::splat(half_player_size));
+
+    teleport(&mut app, player, arena_size + 100.0);
+    tick(&mut app, 1);
+    assert_eq!(position(&app, player), arena_size - half_player_size);
+}
+
+#[test]
+fn player_cannot_walk_out_of_the_arena() {
+    let mut app = test_app();
+    let player = place_player(&mut app, Vec2::new(100.0, 300.0));
+
+    press_key(&mut app, KeyCode::KeyA);
+    tick(&mut app, 60);
+
+    assert_eq!(position(&app, player), Vec2::new(PLAYER_SCALE / 2.0, 300.0));
+}
+
+#[test]
+fn enemies_are_confined_to_the_arena() {
+    let mut app = test_app();
+    let enemy = place_enemy(&mut app, Vec2::new(-50.0, 300.0), Vec2::X, 0.0);
+
+    tick(&mut app, 1);
+
+    assert_eq!(position(&app, enemy), Vec2::new(ENEMY_SCALE / 2.0, 300.0));
+}
+
+#[test]
+fn pausing_freezes_movement() {
+    let mut app = test_app();
+    let player = place_player(&mut app, Vec2::new(400.0, 300.0));
+    let enemy = place_enemy(&mut app, Vec2::new(800.0, 500.0), Vec2::NEG_Y, 100.0);
+
+    press_key(&mut app, KeyCode::KeyD);
+    tick(&mut app, 5);
+    let player_before = position(&app, player);
+    let enemy_before = position(&app, enemy);
+    assert!(player_before.x > 400.0);
+    assert!(enemy_before.y < 500.0);
+
+    set_simulation_state(&mut app, SimulationState::Paused);
+    let player_paused = position(&app, player);
+    let enemy_paused = position(&app, enemy);
+    tick(&mut app, 30);
+
+    assert_eq!(position(&app, player), player_paused);
+    assert_eq!(position(&app, enemy), enemy_paused);
+
+    set_simulation_state(&mut app, SimulationState::Running);
+    tick(&mut app, 5);
+
+    assert!(position(&app, player).x > player_paused.x);
+    assert!(position(&app, enemy).y < enemy_paused.y);
+}
+
+#[test]
+fn enemies_spawn_at_the_balanced_rate() {
+    let mut app = test_app_with_balance(Balance {
+        enemy_spawn_time: 0.5,
+        ..default()
+    });
+    let timestep = app.world().resource::<Time<Fixed>>().timestep();
+
+    // Two spawns, with some slack so neither lands right on the last tick
+    tick(&mut app, (1.25 / timestep.as_secs_f32()) as u32);
+
+    assert_eq!(count::<Enemy>(&mut app), 2 * ENEMY_SPAWN_OVER_TIME);
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use bevy::prelude::*;
+use common::*;
+use learn_2_bevy_ball_game::*;
+
+fn ticks_for(seconds: f32, app: &App) -> u32 {
+    let timestep = app.world().resource::<Time<Fixed>>().timestep();
+    (seconds / timestep.as_secs_f32()).ceil() as u32 + 1
+}
+
+fn tracks(app: &mut App) -> Vec<(AppState, f32)> {
+    app.world_mut()
+        .query::<&MusicTrack>()
+        .iter(app.world())
+        .map(|track| (track.state.clone(), track.level))
+        .collect()
+}
+
+#[test]
+fn state_changes_crossfade_to_the_new_track() {
+    let mut app = test_app();
+    let ticks = ticks_for(MUSIC_CROSSFADE_TIME, &app);
+    tick(&mut app, ticks);
+    assert_eq!(tracks(&mut app), vec![(AppState::Game, 1.0)]);
+
+    app.insert_resource(NextState::Pending(AppState::GameOver));
+    tick(&mut app, ticks / 2);
+    let mut halfway = tracks(&mut app);
+    halfway.sort_by(|a, b| a.1.total_cmp(&b.1));
+    assert_eq!(halfway.len(), 2);
+    assert_eq!(halfway[0].0, AppState::Game);
+    assert_eq!(halfway[1].0, AppState::GameOver);
+    assert!(halfway[0].1 > 0.0 && halfway[1].1 < 1.0);
+
+    tick(&mut app, ticks);
+    assert_eq!(tracks(&mut app), vec![(AppState::GameOver, 1.0)]);
+}
+
+#[test]
+fn music_ducks_while_paused() {
+    let mut app = test_app();
+    let ticks = ticks_for(MUSIC_DUCK_TIME, &app);
+
+    set_simulation_state(&mut app, SimulationState::Paused);
+    tick(&mut app, ticks);
+    assert_eq!(
+        app.world().resource::<MusicDuck>().level,
+        MUSIC_PAUSED_LEVEL
+    );
+
+    set_simulation_state(&mut app, SimulationState::Running);
+    tick(&mut app, ticks);
+    assert_eq!(app.world().resource::<MusicDuck>().level, 1.0);
+}
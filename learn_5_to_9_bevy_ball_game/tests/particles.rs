@@ -0,0 +1,37 @@
+mod common;
+
+use bevy::prelude::*;
+use common::*;
+use learn_2_bevy_ball_game::*;
+
+#[test]
+fn collecting_a_star_bursts_into_particles_that_fade_away() {
+    let mut app = test_app();
+    place_player(&mut app, Vec2::new(400.0, 300.0));
+    place_star(&mut app, Vec2::new(410.0, 300.0));
+    tick(&mut app, 2);
+
+    assert_eq!(
+        count::<Particle>(&mut app),
+        ParticleEffect::StarCollected.emitter().count
+    );
+
+    // Outlives the longest lived particle
+    tick(&mut app, 60);
+    assert_eq!(count::<Particle>(&mut app), 0);
+}
+
+#[test]
+fn particles_stay_within_the_budget() {
+    let mut app = test_app();
+    for _ in 0..PARTICLE_BUDGET {
+        app.world_mut().send_event(ParticleEvent {
+            effect: ParticleEffect::PlayerDied,
+            position: Vec2::new(400.0, 300.0),
+            direction: Vec2::ZERO,
+        });
+    }
+    tick(&mut app, 1);
+
+    assert_eq!(count::<Particle>(&mut app), PARTICLE_BUDGET);
+}
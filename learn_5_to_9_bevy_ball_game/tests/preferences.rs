@@ -0,0 +1,134 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use common::*;
+use learn_2_bevy_ball_game::*;
+
+#[test]
+fn preferences_round_trip_and_fill_in_missing_fields() {
+    let preferences = Preferences {
+        music_volume: 0.3,
+        window_mode: PreferredWindowMode::BorderlessFullscreen,
+        control_scheme: ControlScheme::Arrows,
+        enemy_bounce_sound: false,
+        ..default()
+    };
+
+    let path = std::env::temp_dir().join("ball_game_preferences.ron");
+    preferences.save(&path).unwrap();
+    assert_eq!(Preferences::load(&path).unwrap(), preferences);
+
+    std::fs::write(&path, "(vsync: false)").unwrap();
+    let partial = Preferences::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(
+        partial,
+        Preferences {
+            vsync: false,
+            ..default()
+        }
+    );
+}
+
+#[test]
+fn settings_step_through_their_options() {
+    let mut preferences = Preferences {
+        sfx_volume: 0.9,
+        ..default()
+    };
+
+    SettingsRow::SfxVolume.adjust(&mut preferences, 1);
+    SettingsRow::SfxVolume.adjust(&mut preferences, 1);
+    assert_eq!(preferences.sfx_volume, 1.0);
+    SettingsRow::SfxVolume.adjust(&mut preferences, -1);
+    assert_eq!(SettingsRow::SfxVolume.value(&preferences), "90%");
+
+    SettingsRow::Resolution.adjust(&mut preferences, -1);
+    assert_eq!(
+        preferences.resolution,
+        WINDOW_RESOLUTIONS[WINDOW_RESOLUTIONS.len() - 1]
+    );
+
+    SettingsRow::ControlScheme.adjust(&mut preferences, 1);
+    assert_eq!(preferences.control_scheme, ControlScheme::Wasd);
+}
+
+#[test]
+fn control_scheme_picks_the_movement_keys() {
+    let mut app = test_app();
+    app.world_mut().resource_mut::<Preferences>().control_scheme = ControlScheme::Arrows;
+
+    press_key(&mut app, KeyCode::KeyW);
+    tick(&mut app, 1);
+    assert_eq!(app.world().resource::<PlayerInput>().movement, Vec2::ZERO);
+
+    press_key(&mut app, KeyCode::ArrowUp);
+    tick(&mut app, 1);
+    assert_eq!(app.world().resource::<PlayerInput>().movement, Vec2::Y);
+}
+
+#[test]
+fn window_keeps_its_size_when_other_preferences_change() {
+    let mut app = test_app();
+    let window = app
+        .world_mut()
+        .spawn((Window::default(), PrimaryWindow))
+        .id();
+    let apply = app.register_system(apply_window_preferences);
+
+    app.world_mut().run_system(apply).unwrap();
+    let [width, height] = Preferences::default().resolution;
+    assert_eq!(
+        app.world().get::<Window>(window).unwrap().resolution.size(),
+        Vec2::new(width as f32, height as f32)
+    );
+
+    // The player drags the window bigger, then turns the music down
+    let mut window_mut = app.world_mut().get_mut::<Window>(window).unwrap();
+    window_mut.resolution.set(1000.0, 700.0);
+    app.world_mut().resource_mut::<Preferences>().music_volume = 0.2;
+    app.world_mut().run_system(apply).unwrap();
+    assert_eq!(
+        app.world().get::<Window>(window).unwrap().resolution.size(),
+        Vec2::new(1000.0, 700.0)
+    );
+
+    app.world_mut().resource_mut::<Preferences>().resolution = WINDOW_RESOLUTIONS[1];
+    app.world_mut().run_system(apply).unwrap();
+    let [width, height] = WINDOW_RESOLUTIONS[1];
+    assert_eq!(
+        app.world().get::<Window>(window).unwrap().resolution.size(),
+        Vec2::new(width as f32, height as f32)
+    );
+}
+
+#[test]
+fn settings_open_from_the_pause_screen() {
+    let mut app = test_app();
+    app.add_plugins((SettingsMenuPlugin, StatesPlugin));
+
+    tap_key(&mut app, SETTINGS_KEY);
+    assert_eq!(
+        *app.world().resource::<State<SettingsState>>().get(),
+        SettingsState::Closed
+    );
+
+    set_simulation_state(&mut app, SimulationState::Paused);
+    tap_key(&mut app, SETTINGS_KEY);
+    assert_eq!(
+        *app.world().resource::<State<SettingsState>>().get(),
+        SettingsState::Open
+    );
+
+    tap_key(&mut app, KeyCode::ArrowDown);
+    tap_key(&mut app, KeyCode::ArrowLeft);
+    assert_eq!(app.world().resource::<Preferences>().sfx_volume, 0.9);
+
+    // Space doesn't unpause the game behind the settings screen
+    tap_key(&mut app, KeyCode::Space);
+    assert_eq!(
+        *app.world().resource::<State<SimulationState>>().get(),
+        SimulationState::Paused
+    );
+}
@@ -0,0 +1,114 @@
+use bevy::prelude::*;
+use learn_2_bevy_ball_game::*;
+use std::io::ErrorKind;
+
+const REPLAY_SEED: u64 = 7;
+const REPLAY_SECONDS: f32 = 30.0;
+
+fn sample_replay() -> Replay {
+    Replay {
+        seed: 42,
+        arena: Arena::large(),
+        frames: vec![
+            PlayerInput::default(),
+            PlayerInput {
+                movement: Vec2::new(1.0, -1.0),
+                sprint: true,
+                ..default()
+            },
+            PlayerInput {
+                movement: Vec2::new(-1.0, 1.0),
+                dash: true,
+                shield: true,
+                ..default()
+            },
+        ],
+    }
+}
+
+#[test]
+fn replay_round_trips_through_bytes() {
+    let replay = sample_replay();
+
+    assert_eq!(Replay::from_bytes(&replay.to_bytes()).unwrap(), replay);
+}
+
+#[test]
+fn replay_rejects_a_bad_magic() {
+    let mut bytes = sample_replay().to_bytes();
+    bytes[0] = b'X';
+
+    let error = Replay::from_bytes(&bytes).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn replay_rejects_an_unknown_version() {
+    let mut bytes = sample_replay().to_bytes();
+    bytes[4] = bytes[4].wrapping_add(1);
+
+    let error = Replay::from_bytes(&bytes).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn replay_rejects_a_truncated_header() {
+    let replay = sample_replay();
+    let bytes = replay.to_bytes();
+    // Everything but the last byte of the arena size
+    let header = &bytes[..bytes.len() - replay.frames.len() - 1];
+
+    let error = Replay::from_bytes(header).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn replayed_run_ends_like_the_recorded_one() {
+    let mut recording = headless_app();
+    recording
+        .insert_resource(GameRng::new(REPLAY_SEED))
+        .insert_resource(Arena::large())
+        .insert_resource(ReplayRecorder {
+            path: std::env::temp_dir().join("ball_game_unsaved.replay"),
+            frames: Vec::new(),
+        })
+        .add_plugins(BotPlugin)
+        .add_systems(
+            FixedPreUpdate,
+            record_player_input
+                .run_if(in_state(AppState::Game))
+                .run_if(in_state(SimulationState::Running)),
+        );
+    let recorded = simulate(&mut recording, REPLAY_SECONDS);
+
+    let replay = Replay {
+        seed: REPLAY_SEED,
+        arena: *recording.world().resource::<Arena>(),
+        frames: recording
+            .world()
+            .resource::<ReplayRecorder>()
+            .frames
+            .clone(),
+    };
+    assert!(!replay.frames.is_empty());
+
+    let mut replaying = headless_app();
+    replaying
+        .insert_resource(GameRng::new(replay.seed))
+        .insert_resource(replay.arena)
+        .insert_resource(ReplayPlayer {
+            replay,
+            next_frame: 0,
+        })
+        .add_systems(
+            FixedPreUpdate,
+            inject_replay_input
+                .run_if(in_state(AppState::Game))
+                .run_if(in_state(SimulationState::Running)),
+        );
+    let replayed = simulate(&mut replaying, REPLAY_SECONDS);
+
+    assert_eq!(replayed.score, recorded.score);
+    assert_eq!(replayed.survived_seconds, recorded.survived_seconds);
+    assert_eq!(replayed.game_over, recorded.game_over);
+}
@@ -0,0 +1,145 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use common::*;
+use learn_2_bevy_ball_game::*;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Mutex, PoisonError};
+
+#[test]
+fn save_file_round_trips() {
+    let mut app = headless_app();
+    simulate(&mut app, 3.0);
+    let save_game = SaveGame::capture(app.world_mut());
+
+    let path = std::env::temp_dir().join("ball_game_round_trip.ron");
+    save_game.save(&path).unwrap();
+    let loaded = SaveGame::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded, save_game);
+}
+
+// Tests that use the real save file take turns with it, and put back any save that was
+// there before
+fn with_save_file(test: impl FnOnce(&SaveGame)) {
+    static SAVE_FILE_LOCK: Mutex<()> = Mutex::new(());
+    let _lock = SAVE_FILE_LOCK
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    let existing = std::fs::read(SAVE_FILE_PATH).ok();
+
+    let mut app = test_app_with_seed(5);
+    tick(&mut app, 50);
+    let save_game = SaveGame::capture(app.world_mut());
+    save_game.save(SAVE_FILE_PATH).unwrap();
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| test(&save_game)));
+
+    match existing {
+        Some(bytes) => std::fs::write(SAVE_FILE_PATH, bytes).unwrap(),
+        None => std::fs::remove_file(SAVE_FILE_PATH).unwrap(),
+    }
+    if let Err(panic) = result {
+        panic::resume_unwind(panic);
+    }
+}
+
+fn unsaved_recorder() -> ReplayRecorder {
+    ReplayRecorder {
+        path: std::env::temp_dir().join("ball_game_unsaved.replay"),
+        frames: Vec::new(),
+    }
+}
+
+#[test]
+fn recorded_runs_cannot_continue_a_saved_game() {
+    with_save_file(|_| {
+        let mut app = headless_app();
+        let can_continue = |app: &mut App| {
+            app.world_mut()
+                .run_system_cached(can_continue_saved_game)
+                .unwrap()
+        };
+
+        assert!(can_continue(&mut app));
+        app.insert_resource(unsaved_recorder());
+        assert!(!can_continue(&mut app));
+    });
+}
+
+#[test]
+fn finished_replay_leaves_the_save_file_alone() {
+    with_save_file(|save_game| {
+        let mut app = headless_app();
+        app.add_plugins(SavePlugin)
+            .insert_resource(ReplayPlayer {
+                replay: Replay {
+                    seed: TEST_SEED,
+                    arena: Arena::default(),
+                    frames: vec![PlayerInput::default(); 10],
+                },
+                next_frame: 0,
+            })
+            .add_systems(
+                FixedPreUpdate,
+                inject_replay_input
+                    .run_if(in_state(AppState::Game))
+                    .run_if(in_state(SimulationState::Running)),
+            );
+        let timestep = app.world().resource::<Time<Fixed>>().timestep();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(timestep));
+
+        tick(&mut app, 20);
+
+        assert_eq!(
+            *app.world().resource::<State<SimulationState>>().get(),
+            SimulationState::Paused
+        );
+        assert_eq!(SaveGame::load(SAVE_FILE_PATH).unwrap(), *save_game);
+    });
+}
+
+#[test]
+fn save_is_only_continued_in_the_arena_it_was_played_in() {
+    with_save_file(|_| {
+        let continues = |arena: Arena| {
+            let mut app = headless_app();
+            app.insert_resource(arena);
+            app.world_mut()
+                .run_system_cached(continue_saved_game)
+                .unwrap();
+
+            matches!(
+                app.world().resource::<NextState<SimulationState>>(),
+                NextState::Pending(SimulationState::Paused)
+            )
+        };
+
+        assert!(continues(Arena::default()));
+        assert!(!continues(Arena::large()));
+    });
+}
+
+#[test]
+fn restored_run_plays_out_like_the_original() {
+    let mut original = test_app_with_seed(3);
+    tick(&mut original, 200);
+    let save_game = SaveGame::capture(original.world_mut());
+
+    let mut restored = test_app_with_seed(4);
+    save_game.restore(restored.world_mut());
+    assert_eq!(
+        sorted(SaveGame::capture(restored.world_mut())),
+        sorted(save_game)
+    );
+
+    tick(&mut original, 300);
+    tick(&mut restored, 300);
+
+    assert_eq!(
+        sorted(SaveGame::capture(restored.world_mut())),
+        sorted(SaveGame::capture(original.world_mut()))
+    );
+}
@@ -0,0 +1,44 @@
+mod common;
+
+use common::*;
+use learn_2_bevy_ball_game::*;
+
+#[test]
+fn exported_world_loads_back_the_same() {
+    let mut original = test_app_with_seed(5);
+    tick(&mut original, 150);
+    let scene_text = export_world_scene(original.world_mut()).unwrap();
+
+    let mut loaded = test_app_with_seed(6);
+    load_world_scene(loaded.world_mut(), &scene_text).unwrap();
+
+    // The scene holds the world, not the random stream, so leave that out
+    let original_state = SaveGame {
+        seed: 0,
+        rng_position: 0,
+        ..sorted(SaveGame::capture(original.world_mut()))
+    };
+    let loaded_state = SaveGame {
+        seed: 0,
+        rng_position: 0,
+        ..sorted(SaveGame::capture(loaded.world_mut()))
+    };
+    assert_eq!(loaded_state, original_state);
+}
+
+#[test]
+fn hand_authored_layout_replaces_the_arena() {
+    let mut app = test_app_with_seed(5);
+    let layout = std::fs::read_to_string("scenes/example_layout.scn.ron").unwrap();
+
+    load_world_scene(app.world_mut(), &layout).unwrap();
+    tick(&mut app, 1);
+
+    assert_eq!(count::<Player>(&mut app), 1);
+    assert_eq!(count::<Enemy>(&mut app), 2);
+    assert_eq!(count::<Star>(&mut app), 3);
+
+    let world = app.world_mut();
+    let lives = world.query::<&Lives>().single(world).unwrap();
+    assert_eq!(lives.remaining, 5);
+}
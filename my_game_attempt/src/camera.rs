@@ -1,17 +1,260 @@
+use crate::player::Player;
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
 use bevy::prelude::*;
 
-pub fn spawn_camera() {}
-pub fn move_camera() {}
-pub fn camera_follow_player(cfp: Res<CameraFollowPlayer>) {}
-
-#[derive(Resource)]
-pub struct CameraFollowPlayer(pub bool);
+// How long the camera takes to catch up with the player, roughly
+pub const CAMERA_SMOOTH_TIME: f32 = 0.25;
+// The player can move this far around the middle of the screen before the camera follows
+pub const CAMERA_DEAD_ZONE: Vec2 = Vec2::new(160.0, 90.0);
+pub const MIN_ZOOM: f32 = 0.5;
+pub const MAX_ZOOM: f32 = 3.0;
+pub const ZOOM_STEP: f32 = 0.1;
+pub const ZOOM_SPEED: f32 = 8.0;
+pub const MAX_SHAKE_OFFSET: f32 = 24.0;
+pub const SHAKE_DECAY: f32 = 1.5;
+pub const SHAKE_FREQUENCY: f32 = 25.0;
 
 pub struct CameraPlugin;
 
 impl Plugin for CameraPlugin {
     fn build(&self, app: &mut bevy::app::App) {
-        app.add_systems(Startup, spawn_camera)
-            .add_systems(Update, move_camera);
+        app.insert_resource(CameraFollowPlayer(true))
+            .init_resource::<CameraBounds>()
+            .add_event::<CameraZoom>()
+            .add_event::<CameraShake>()
+            .add_systems(Startup, spawn_camera)
+            .add_systems(
+                Update,
+                (
+                    camera_follow_player.run_if(|cfp: Res<CameraFollowPlayer>| cfp.0),
+                    zoom_camera,
+                    shake_camera,
+                    move_camera,
+                )
+                    .chain(),
+            );
+    }
+}
+
+#[derive(Resource)]
+pub struct CameraFollowPlayer(pub bool);
+
+// The part of the world the camera may show. `None` lets it go anywhere.
+#[derive(Resource, Default)]
+pub struct CameraBounds(pub Option<Rect>);
+
+// Zooms to `zoom`, where 1.0 is one world unit per pixel and larger shows more
+#[derive(Event)]
+pub struct CameraZoom {
+    pub zoom: f32,
+}
+
+// Shakes the screen. Intensities add up, 1.0 is the strongest shake.
+#[derive(Event)]
+pub struct CameraShake {
+    pub intensity: f32,
+}
+
+#[derive(Component)]
+pub struct CameraRig {
+    // Where the camera is heading, and where it is before shaking
+    pub focus: Vec2,
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub zoom: f32,
+    pub target_zoom: f32,
+    pub trauma: f32,
+}
+
+impl Default for CameraRig {
+    fn default() -> Self {
+        Self {
+            focus: Vec2::ZERO,
+            position: Vec2::ZERO,
+            velocity: Vec2::ZERO,
+            zoom: 1.0,
+            target_zoom: 1.0,
+            trauma: 0.0,
+        }
+    }
+}
+
+pub fn spawn_camera(mut commands: Commands) {
+    commands.spawn((Camera2d, CameraRig::default()));
+}
+
+// Moves the focus only once the player leaves the dead zone around it
+pub fn camera_follow_player(
+    player_query: Query<&Transform, With<Player>>,
+    mut rig_query: Query<&mut CameraRig>,
+) {
+    let Ok(player_transform) = player_query.single() else {
+        return;
+    };
+    let Ok(mut rig) = rig_query.single_mut() else {
+        return;
+    };
+
+    let player_position = player_transform.translation.truncate();
+    let half_dead_zone = CAMERA_DEAD_ZONE * rig.zoom / 2.0;
+    let offset = player_position - rig.focus;
+
+    rig.focus += offset - offset.clamp(-half_dead_zone, half_dead_zone);
+}
+
+pub fn zoom_camera(
+    mut mouse_wheel_reader: EventReader<MouseWheel>,
+    mut zoom_reader: EventReader<CameraZoom>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut rig_query: Query<&mut CameraRig>,
+    time: Res<Time>,
+) {
+    let Ok(mut rig) = rig_query.single_mut() else {
+        return;
+    };
+
+    for zoom in zoom_reader.read() {
+        rig.target_zoom = zoom.zoom;
+    }
+
+    let mut steps = 0.0;
+    for wheel in mouse_wheel_reader.read() {
+        steps -= match wheel.unit {
+            MouseScrollUnit::Line => wheel.y,
+            MouseScrollUnit::Pixel => wheel.y / 100.0,
+        };
+    }
+    if keyboard_input.just_pressed(KeyCode::Minus) {
+        steps += 1.0;
+    }
+    if keyboard_input.just_pressed(KeyCode::Equal) {
+        steps -= 1.0;
+    }
+
+    // Each step zooms by the same ratio, however far in or out the camera is
+    rig.target_zoom = (rig.target_zoom * (1.0 + ZOOM_STEP).powf(steps)).clamp(MIN_ZOOM, MAX_ZOOM);
+    rig.zoom = rig.zoom.lerp(
+        rig.target_zoom,
+        1.0 - (-ZOOM_SPEED * time.delta_secs()).exp(),
+    );
+}
+
+pub fn shake_camera(
+    mut shake_reader: EventReader<CameraShake>,
+    mut rig_query: Query<&mut CameraRig>,
+    time: Res<Time>,
+) {
+    let Ok(mut rig) = rig_query.single_mut() else {
+        return;
+    };
+
+    for shake in shake_reader.read() {
+        rig.trauma += shake.intensity;
+    }
+    rig.trauma = (rig.trauma - SHAKE_DECAY * time.delta_secs()).clamp(0.0, 1.0);
+}
+
+pub fn move_camera(
+    mut camera_query: Query<(&mut CameraRig, &mut Transform, &mut Projection, &Camera)>,
+    bounds: Res<CameraBounds>,
+    time: Res<Time>,
+) {
+    let Ok((mut rig, mut transform, mut projection, camera)) = camera_query.single_mut() else {
+        return;
+    };
+
+    let (focus, position, velocity) = (rig.focus, rig.position, rig.velocity);
+    let (position, velocity) = smooth_damp(
+        position,
+        focus,
+        velocity,
+        CAMERA_SMOOTH_TIME,
+        time.delta_secs(),
+    );
+    rig.velocity = velocity;
+    rig.position = match (bounds.0, camera.logical_viewport_size()) {
+        (Some(bounds), Some(viewport_size)) => {
+            clamp_to_bounds(position, viewport_size * rig.zoom / 2.0, bounds)
+        }
+        _ => position,
+    };
+
+    if let Projection::Orthographic(orthographic) = projection.as_mut() {
+        orthographic.scale = rig.zoom;
+    }
+
+    // Trauma squared keeps small shakes subtle. Offset sine waves stand in for noise.
+    let shake = rig.trauma * rig.trauma;
+    let t = time.elapsed_secs() * SHAKE_FREQUENCY;
+    let shake_offset = Vec2::new((t * 1.1).sin() + (t * 2.3).sin() * 0.5, (t * 1.7).cos())
+        * MAX_SHAKE_OFFSET
+        * shake;
+
+    transform.translation = (rig.position + shake_offset).extend(transform.translation.z);
+}
+
+// A critically damped spring: gets to `target` as fast as it can without overshooting
+pub fn smooth_damp(
+    current: Vec2,
+    target: Vec2,
+    velocity: Vec2,
+    smooth_time: f32,
+    delta: f32,
+) -> (Vec2, Vec2) {
+    let omega = 2.0 / smooth_time.max(0.0001);
+    let x = omega * delta;
+    let decay = 1.0 / (1.0 + x + 0.48 * x * x + 0.235 * x * x * x);
+
+    let change = current - target;
+    let temp = (velocity + omega * change) * delta;
+
+    let velocity = (velocity - omega * temp) * decay;
+    let position = target + (change + temp) * decay;
+    (position, velocity)
+}
+
+// Keeps the view inside the bounds, or centered on them when they are smaller than it
+fn clamp_to_bounds(position: Vec2, half_view: Vec2, bounds: Rect) -> Vec2 {
+    let clamp_axis = |position: f32, half_view: f32, min: f32, max: f32| {
+        if max - min <= half_view * 2.0 {
+            (min + max) / 2.0
+        } else {
+            position.clamp(min + half_view, max - half_view)
+        }
+    };
+
+    Vec2::new(
+        clamp_axis(position.x, half_view.x, bounds.min.x, bounds.max.x),
+        clamp_axis(position.y, half_view.y, bounds.min.y, bounds.max.y),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn smooth_damp_settles_on_the_target_without_overshooting() {
+        let target = Vec2::new(300.0, -120.0);
+        let (mut position, mut velocity) = (Vec2::ZERO, Vec2::ZERO);
+
+        for _ in 0..240 {
+            (position, velocity) =
+                smooth_damp(position, target, velocity, CAMERA_SMOOTH_TIME, 1.0 / 60.0);
+
+            assert!(position.x <= target.x && position.y >= target.y);
+        }
+
+        assert!(position.distance(target) < 0.01);
+    }
+
+    #[test]
+    fn bounds_smaller_than_the_view_center_the_camera() {
+        let bounds = Rect::new(-100.0, 0.0, 100.0, 2000.0);
+        let half_view = Vec2::new(320.0, 180.0);
+
+        let position = clamp_to_bounds(Vec2::new(250.0, -50.0), half_view, bounds);
+
+        assert_eq!(position, Vec2::new(0.0, 180.0));
     }
 }
@@ -0,0 +1,468 @@
+use crate::camera::{CameraBounds, CameraShake};
+use crate::player::{MAX_SPEED, PLAYER_RADIUS, Player, player_movement};
+use bevy::platform::collections::HashSet;
+use bevy::prelude::*;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+// The course is a grid of cells running upwards from the start, which sits at the origin
+pub const CELL_SIZE: f32 = 64.0;
+pub const COURSE_COLUMNS: i32 = 15;
+pub const COURSE_ROWS: i32 = 60;
+// Cells this close to the path are never blocked
+pub const PATH_CLEARANCE: i32 = 1;
+pub const PATH_TURN_CHANCE: f64 = 0.3;
+pub const OBSTACLE_CHANCE: f64 = 0.35;
+pub const TALL_OBSTACLE_CHANCE: f64 = 0.3;
+// Fraction of the speed into an obstacle the player bounces back with
+pub const BOUNCE: f32 = 0.4;
+pub const COLLISION_SHAKE: f32 = 0.6;
+pub const RESTART_KEY: KeyCode = KeyCode::KeyR;
+pub const TIMER_FONT_SIZE: f32 = 24.0;
+
+const WALL_SPRITE: &str = "sprites/block_locked_square.png";
+const START_SPRITE: &str = "sprites/hole_start.png";
+const GOAL_SPRITE: &str = "sprites/hole_large_end.png";
+// Blocks that fit in one cell, and blocks two cells tall
+const SMALL_BLOCKS: [(&str, Vec2); 4] = [
+    ("sprites/block_small.png", Vec2::new(32.0, 32.0)),
+    ("sprites/block_square.png", Vec2::new(64.0, 64.0)),
+    ("sprites/block_locked_small.png", Vec2::new(32.0, 32.0)),
+    ("sprites/block_locked_square.png", Vec2::new(64.0, 64.0)),
+];
+const TALL_BLOCKS: [(&str, Vec2); 3] = [
+    ("sprites/block_narrow.png", Vec2::new(32.0, 128.0)),
+    ("sprites/block_rotate_narrow.png", Vec2::new(32.0, 128.0)),
+    ("sprites/block_large.png", Vec2::new(64.0, 128.0)),
+];
+
+pub struct CoursePlugin;
+
+impl Plugin for CoursePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(CourseSeed(seed_from_args().unwrap_or_else(rand::random)))
+            .init_state::<RunState>()
+            .init_resource::<RunTimer>()
+            .add_systems(Startup, (spawn_obsteceles, spawn_run_timer))
+            .add_systems(
+                FixedUpdate,
+                (
+                    collide_player_with_obstacles.after(player_movement),
+                    finish_run
+                        .after(collide_player_with_obstacles)
+                        .run_if(in_state(RunState::Racing)),
+                ),
+            )
+            .add_systems(
+                Update,
+                (
+                    tick_run_timer.run_if(in_state(RunState::Racing)),
+                    restart_run,
+                    update_run_timer,
+                ),
+            );
+    }
+}
+
+// The same seed always builds the same course
+#[derive(Resource)]
+pub struct CourseSeed(pub u64);
+
+#[derive(States, Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub enum RunState {
+    #[default]
+    Racing,
+    Finished,
+}
+
+#[derive(Resource, Default)]
+pub struct RunTimer {
+    pub elapsed: f32,
+}
+
+// Something the player can't drive through
+#[derive(Component)]
+pub struct Object;
+
+#[derive(Component)]
+pub struct Collider {
+    pub half_size: Vec2,
+}
+
+#[derive(Component)]
+pub struct GoalZone {
+    pub half_size: Vec2,
+}
+
+#[derive(Component)]
+pub struct RunTimerText;
+
+pub struct Block {
+    pub cell: IVec2,
+    pub sprite: &'static str,
+    pub size: Vec2,
+}
+
+pub struct Course {
+    pub start: IVec2,
+    pub goal: IVec2,
+    pub blocks: Vec<Block>,
+}
+
+// Returns the value following `--seed` on the command line
+pub fn seed_from_args() -> Option<u64> {
+    let seed = std::env::args().skip_while(|arg| arg != "--seed").nth(1)?;
+    seed.parse().ok()
+}
+
+pub fn cell_position(cell: IVec2) -> Vec2 {
+    (cell - IVec2::new(COURSE_COLUMNS / 2, 0)).as_vec2() * CELL_SIZE
+}
+
+// Lays a path from the start at the bottom to the goal at the top first, then fills the
+// cells away from it with blocks, so there is always a way through
+pub fn generate_course(seed: u64) -> Course {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+
+    let path = lay_path(&mut rng);
+    let start = path[0];
+    let goal = path[path.len() - 1];
+    let clear = clear_cells(&path);
+
+    let mut blocks = vec![];
+    let mut taken = HashSet::new();
+    for row in 0..COURSE_ROWS {
+        for column in 0..COURSE_COLUMNS {
+            let cell = IVec2::new(column, row);
+            if clear.contains(&cell) || taken.contains(&cell) || !rng.random_bool(OBSTACLE_CHANCE) {
+                continue;
+            }
+
+            let above = cell + IVec2::Y;
+            let fits_tall_block =
+                row + 1 < COURSE_ROWS && !clear.contains(&above) && !taken.contains(&above);
+
+            if fits_tall_block && rng.random_bool(TALL_OBSTACLE_CHANCE) {
+                let (sprite, size) = TALL_BLOCKS[rng.random_range(0..TALL_BLOCKS.len())];
+                taken.insert(above);
+                blocks.push(Block { cell, sprite, size });
+            } else {
+                let (sprite, size) = SMALL_BLOCKS[rng.random_range(0..SMALL_BLOCKS.len())];
+                blocks.push(Block { cell, sprite, size });
+            }
+            taken.insert(cell);
+        }
+    }
+
+    Course {
+        start,
+        goal,
+        blocks,
+    }
+}
+
+// The cells from the start to the goal, one row at a time with sideways turns between
+fn lay_path(rng: &mut ChaCha8Rng) -> Vec<IVec2> {
+    let mut path = vec![];
+    let mut column = COURSE_COLUMNS / 2;
+    for row in 0..COURSE_ROWS {
+        path.push(IVec2::new(column, row));
+
+        if row > 0 && rng.random_bool(PATH_TURN_CHANCE) {
+            let target = rng.random_range(0..COURSE_COLUMNS);
+            while column != target {
+                column += (target - column).signum();
+                path.push(IVec2::new(column, row));
+            }
+        }
+    }
+    path
+}
+
+fn clear_cells(path: &[IVec2]) -> HashSet<IVec2> {
+    let mut clear = HashSet::new();
+    for cell in path {
+        for x in -PATH_CLEARANCE..=PATH_CLEARANCE {
+            for y in -PATH_CLEARANCE..=PATH_CLEARANCE {
+                clear.insert(*cell + IVec2::new(x, y));
+            }
+        }
+    }
+    clear
+}
+
+pub fn spawn_obsteceles(
+    mut commands: Commands,
+    assest_server: Res<AssetServer>,
+    seed: Res<CourseSeed>,
+    mut bounds: ResMut<CameraBounds>,
+) {
+    info!(seed = seed.0, "Generating course");
+    let course = generate_course(seed.0);
+
+    for block in &course.blocks {
+        // Tall blocks sit on their cell and the one above it
+        let position =
+            cell_position(block.cell) + Vec2::new(0.0, (block.size.y - CELL_SIZE).max(0.0) / 2.0);
+        spawn_object(
+            &mut commands,
+            &assest_server,
+            block.sprite,
+            position,
+            block.size,
+        );
+    }
+
+    // A wall all the way around keeps the player on the course
+    for column in -1..=COURSE_COLUMNS {
+        for row in -1..=COURSE_ROWS {
+            if (0..COURSE_COLUMNS).contains(&column) && (0..COURSE_ROWS).contains(&row) {
+                continue;
+            }
+
+            let position = cell_position(IVec2::new(column, row));
+            spawn_object(
+                &mut commands,
+                &assest_server,
+                WALL_SPRITE,
+                position,
+                Vec2::splat(CELL_SIZE),
+            );
+        }
+    }
+
+    commands.spawn((
+        Sprite {
+            image: assest_server.load(START_SPRITE),
+            ..default()
+        },
+        Transform::from_translation(cell_position(course.start).extend(-1.0)),
+    ));
+    commands.spawn((
+        Sprite {
+            image: assest_server.load(GOAL_SPRITE),
+            custom_size: Some(Vec2::splat(CELL_SIZE)),
+            ..default()
+        },
+        Transform::from_translation(cell_position(course.goal).extend(-1.0)),
+        GoalZone {
+            half_size: Vec2::splat(CELL_SIZE / 2.0),
+        },
+    ));
+
+    let min = cell_position(IVec2::new(-1, -1)) - CELL_SIZE / 2.0;
+    let max = cell_position(IVec2::new(COURSE_COLUMNS, COURSE_ROWS)) + CELL_SIZE / 2.0;
+    bounds.0 = Some(Rect::from_corners(min, max));
+}
+
+fn spawn_object(
+    commands: &mut Commands,
+    assest_server: &AssetServer,
+    sprite: &'static str,
+    position: Vec2,
+    size: Vec2,
+) {
+    commands.spawn((
+        Sprite {
+            image: assest_server.load(sprite),
+            custom_size: Some(size),
+            ..default()
+        },
+        Transform::from_translation(position.extend(0.0)),
+        Object,
+        Collider {
+            half_size: size / 2.0,
+        },
+    ));
+}
+
+// Pushes the player out of any block it drove into and bounces it off
+pub fn collide_player_with_obstacles(
+    mut player_query: Query<(&mut Transform, &mut Player)>,
+    object_query: Query<(&Transform, &Collider), Without<Player>>,
+    mut camera_shake_writer: EventWriter<CameraShake>,
+) {
+    for (mut player_transform, mut player) in &mut player_query {
+        let mut position = player_transform.translation.truncate();
+        let mut impact: f32 = 0.0;
+
+        for (object_transform, collider) in &object_query {
+            let center = object_transform.translation.truncate();
+            let closest = position.clamp(center - collider.half_size, center + collider.half_size);
+            let offset = position - closest;
+            let distance = offset.length();
+            if distance >= PLAYER_RADIUS {
+                continue;
+            }
+
+            // Inside the block there is no closest edge, so it leaves the way it came
+            let normal = if distance > 0.0 {
+                offset / distance
+            } else {
+                (-player.velocity).normalize_or(Vec2::Y)
+            };
+            position += normal * (PLAYER_RADIUS - distance);
+
+            let speed_into = -player.velocity.dot(normal);
+            if speed_into > 0.0 {
+                player.velocity += normal * speed_into * (1.0 + BOUNCE);
+                impact = impact.max(speed_into);
+            }
+        }
+
+        player_transform.translation = position.extend(player_transform.translation.z);
+        if impact > 0.0 {
+            camera_shake_writer.write(CameraShake {
+                intensity: impact / MAX_SPEED * COLLISION_SHAKE,
+            });
+        }
+    }
+}
+
+pub fn tick_run_timer(mut run_timer: ResMut<RunTimer>, time: Res<Time>) {
+    run_timer.elapsed += time.delta_secs();
+}
+
+pub fn finish_run(
+    mut commands: Commands,
+    player_query: Query<&Transform, With<Player>>,
+    goal_query: Query<(&Transform, &GoalZone)>,
+    run_timer: Res<RunTimer>,
+) {
+    for player_transform in &player_query {
+        for (goal_transform, goal) in &goal_query {
+            let offset =
+                player_transform.translation.truncate() - goal_transform.translation.truncate();
+            if offset.abs().cmple(goal.half_size).all() {
+                info!(time = run_timer.elapsed, "Finished the course");
+                commands.insert_resource(NextState::Pending(RunState::Finished));
+            }
+        }
+    }
+}
+
+// Puts the player back at the start of the same course
+pub fn restart_run(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut player_query: Query<(&mut Transform, &mut Player)>,
+    mut run_timer: ResMut<RunTimer>,
+) {
+    if !keyboard_input.just_pressed(RESTART_KEY) {
+        return;
+    }
+
+    for (mut transform, mut player) in &mut player_query {
+        *transform = Transform::default();
+        *player = Player::default();
+    }
+    *run_timer = RunTimer::default();
+    commands.insert_resource(NextState::Pending(RunState::Racing));
+}
+
+pub fn spawn_run_timer(mut commands: Commands) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(10.0),
+            top: Val::Px(10.0),
+            ..default()
+        },
+        Text::default(),
+        TextFont {
+            font_size: TIMER_FONT_SIZE,
+            ..default()
+        },
+        RunTimerText,
+    ));
+}
+
+pub fn update_run_timer(
+    run_timer: Res<RunTimer>,
+    run_state: Res<State<RunState>>,
+    mut text_query: Query<&mut Text, With<RunTimerText>>,
+) {
+    for mut text in &mut text_query {
+        text.0 = match run_state.get() {
+            RunState::Racing => format!("{:.1}", run_timer.elapsed),
+            RunState::Finished => format!(
+                "Finished in {:.2}s, press R to race again",
+                run_timer.elapsed
+            ),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::PHYSICS_HZ;
+    use std::collections::VecDeque;
+
+    // The cells a block covers, two for the tall ones
+    fn covered_cells(block: &Block) -> Vec<IVec2> {
+        if block.size.y > CELL_SIZE {
+            vec![block.cell, block.cell + IVec2::Y]
+        } else {
+            vec![block.cell]
+        }
+    }
+
+    #[test]
+    fn goal_is_reachable_from_the_start() {
+        for seed in 0..200 {
+            let course = generate_course(seed);
+            let blocked: HashSet<IVec2> = course.blocks.iter().flat_map(covered_cells).collect();
+
+            let mut reached: HashSet<IVec2> = HashSet::from_iter([course.start]);
+            let mut frontier = VecDeque::from([course.start]);
+            while let Some(cell) = frontier.pop_front() {
+                for step in [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y] {
+                    let next = cell + step;
+                    let on_course =
+                        (0..COURSE_COLUMNS).contains(&next.x) && (0..COURSE_ROWS).contains(&next.y);
+                    if on_course && !blocked.contains(&next) && reached.insert(next) {
+                        frontier.push_back(next);
+                    }
+                }
+            }
+
+            assert!(
+                reached.contains(&course.goal),
+                "seed {seed} has no way through"
+            );
+        }
+    }
+
+    #[test]
+    fn one_physics_step_never_moves_the_player_past_its_radius() {
+        let step = MAX_SPEED / PHYSICS_HZ as f32;
+
+        assert!(
+            step < PLAYER_RADIUS,
+            "{step} px per step tunnels through blocks"
+        );
+    }
+
+    #[test]
+    fn blocks_keep_clear_of_the_path() {
+        for seed in 0..200 {
+            let course = generate_course(seed);
+            // Laying the path takes the first draws, so the same seed lays it again
+            let clear = clear_cells(&lay_path(&mut ChaCha8Rng::seed_from_u64(seed)));
+
+            for block in &course.blocks {
+                for cell in covered_cells(block) {
+                    assert!(
+                        !clear.contains(&cell),
+                        "seed {seed} blocks {cell} next to the path"
+                    );
+                    assert!(
+                        cell.y < COURSE_ROWS,
+                        "seed {seed} has a block off the course"
+                    );
+                }
+            }
+        }
+    }
+}
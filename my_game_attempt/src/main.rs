@@ -1,22 +1,14 @@
 mod camera;
+mod course;
 mod player;
 
 use bevy::prelude::*;
+use camera::CameraPlugin;
+use course::CoursePlugin;
+use player::PlayerPlugin;
 
 fn main() {
     App::new()
-        .add_plugins(DefaultPlugins)
-        .add_systems(Startup, (spawn_obsteceles, spawn_player))
-        .add_systems(Update, player_movement)
+        .add_plugins((DefaultPlugins, CameraPlugin, CoursePlugin, PlayerPlugin))
         .run();
 }
-
-
-
-#[derive(Component)]
-pub struct Object;
-
-
-
-pub fn spawn_obsteceles() {}
-
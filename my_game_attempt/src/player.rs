@@ -1,22 +1,100 @@
+use crate::course::RunState;
 use bevy::prelude::*;
 
-#[derive(Component)]
+pub const PLAYER_SIZE: Vec2 = Vec2::new(20.0, 36.0);
+pub const PLAYER_COLOR: Color = Color::srgb(0.9, 0.3, 0.2);
+// The player collides as a circle, which keeps it from snagging on corners
+pub const PLAYER_RADIUS: f32 = 14.0;
+// Radians per second, reached once the player is going `FULL_TURN_SPEED`
+pub const TURN_RATE: f32 = 3.5;
+pub const FULL_TURN_SPEED: f32 = 120.0;
+pub const THRUST: f32 = 600.0;
+pub const BRAKE: f32 = 900.0;
+pub const MAX_SPEED: f32 = 500.0;
+// Fraction of the velocity lost per second, forwards and sideways. Sideways is much
+// higher so the player goes where it points instead of sliding like on ice.
+pub const DRAG: f32 = 0.6;
+pub const SIDEWAYS_DRAG: f32 = 0.98;
+// Movement and collisions run this many times a second whatever the frame rate, so one
+// step never carries the player further than its radius and through a block
+pub const PHYSICS_HZ: f64 = 64.0;
+
+#[derive(Component, Default)]
 pub struct Player {
-    velocity: Vec2,
-    rotation: u32,
+    pub velocity: Vec2,
+    // Radians counterclockwise from facing up
+    pub rotation: f32,
+}
+
+impl Player {
+    pub fn forward(&self) -> Vec2 {
+        Vec2::from_angle(self.rotation).rotate(Vec2::Y)
+    }
+}
+
+pub fn spawn_player(mut commands: Commands) {
+    commands.spawn((
+        Sprite::from_color(PLAYER_COLOR, PLAYER_SIZE),
+        Transform::default(),
+        Player::default(),
+    ));
 }
-pub fn spawn_player() {}
 
 pub fn player_movement(
-    players: Query<&mut Transform, With<Player>>,
+    mut players: Query<(&mut Transform, &mut Player)>,
     input: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
 ) {
+    let delta = time.delta_secs();
+
+    for (mut transform, mut player) in &mut players {
+        let forward = player.forward();
+        let forward_speed = player.velocity.dot(forward);
+
+        // Like a car it only turns while moving
+        let mut turn = 0.0;
+        if input.any_pressed([KeyCode::KeyA, KeyCode::ArrowLeft]) {
+            turn += 1.0;
+        }
+        if input.any_pressed([KeyCode::KeyD, KeyCode::ArrowRight]) {
+            turn -= 1.0;
+        }
+        let grip = (forward_speed / FULL_TURN_SPEED).clamp(-1.0, 1.0);
+        player.rotation =
+            (player.rotation + turn * grip * TURN_RATE * delta).rem_euclid(std::f32::consts::TAU);
+
+        // Whatever isn't along the new heading is sideways, and mostly lost to grip
+        let forward = player.forward();
+        let mut forward_speed = player.velocity.dot(forward);
+        let mut sideways_speed = player.velocity.dot(forward.perp());
+
+        if input.any_pressed([KeyCode::KeyW, KeyCode::ArrowUp]) {
+            forward_speed += THRUST * delta;
+        }
+        // Braking stops the player, it never reverses
+        if input.any_pressed([KeyCode::KeyS, KeyCode::ArrowDown]) {
+            forward_speed = forward_speed.signum() * (forward_speed.abs() - BRAKE * delta).max(0.0);
+        }
+
+        forward_speed *= (1.0 - DRAG).powf(delta);
+        sideways_speed *= (1.0 - SIDEWAYS_DRAG).powf(delta);
+
+        player.velocity =
+            (forward * forward_speed + forward.perp() * sideways_speed).clamp_length_max(MAX_SPEED);
+        transform.translation += (player.velocity * delta).extend(0.0);
+        transform.rotation = Quat::from_rotation_z(player.rotation);
+    }
 }
 
 pub struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(schedule, systems)
+        app.insert_resource(Time::<Fixed>::from_hz(PHYSICS_HZ))
+            .add_systems(Startup, spawn_player)
+            .add_systems(
+                FixedUpdate,
+                player_movement.run_if(in_state(RunState::Racing)),
+            );
     }
-}
\ No newline at end of file
+}